@@ -1,10 +1,11 @@
 use crate::AppSW;
+use crate::utils::to_hex_string;
 use alloc::format;
 use core::str;
 use core::convert::TryInto;
 
 use include_gif::include_gif;
-use ledger_device_sdk::nbgl::{NbglAddressReview, NbglGlyph};
+use ledger_device_sdk::nbgl::{Field, NbglAddressReview, NbglGlyph, NbglReview};
 use crate::alloc::string::ToString;
 
 const DISPLAY_ADDR_BYTES_LEN: usize = 20; // hex fallback (last 20 bytes)
@@ -26,4 +27,28 @@ pub fn ui_display_pk(addr: &[u8]) -> Result<bool, AppSW> {
         .glyph(&FERRIS)
         .verify_str("Verify XELIS address")
         .show(&display_str))
+}
+
+/// Approval screen for an integrated address (one carrying embedded
+/// payment-ID / structured data): shows the decoded address string
+/// alongside the hex encoding of the embedded payload, so the user can see
+/// both halves of what they are about to trust.
+pub fn ui_display_integrated_address(address: &str, integrated_data: &[u8]) -> Result<bool, AppSW> {
+    let data_hex = to_hex_string(integrated_data);
+    let fields = [
+        Field {
+            name: "Address",
+            value: address,
+        },
+        Field {
+            name: "Integrated data",
+            value: data_hex.as_str(),
+        },
+    ];
+
+    let review = NbglReview::new()
+        .titles("Integrated Address", "Confirm embedded data", "Verify")
+        .light();
+
+    Ok(review.show(&fields))
 }
\ No newline at end of file
@@ -7,50 +7,180 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::format;
 
-use crate::utils::to_hex_string;
+use crate::utils::{format_fixed_point, format_fixed_point_trimmed, to_hex_string};
 
 const FIELDS_PER_PAGE: usize = 10;
 
 pub fn ui_display_memo_tx(preview: &MemoPreview) -> Result<bool, AppSW> {
+    if preview.tx_type == TX_INVOKE_CONTRACT {
+        return ui_display_memo_invoke();
+    }
+    if preview.tx_type == TX_DEPLOY_CONTRACT {
+        return ui_display_memo_deploy();
+    }
+
     let ws = memo_ws_mut();
     let total_fields = 4 + ws.outs.len();
+
+    show_paginated_review("Review Transaction", "Sign", total_fields, |i| {
+        build_field_at_index(preview, i)
+    })
+}
+
+/// Walks `field_at` to build `total_fields` worth of fields, showing them
+/// `FIELDS_PER_PAGE` at a time under a shared "Section n/m" subtitle - the
+/// windowed-review pattern every memo preview screen with a host-controlled,
+/// potentially long list (outputs, deposits, ...) uses.
+fn show_paginated_review(
+    title: &str,
+    final_action: &str,
+    total_fields: usize,
+    mut field_at: impl FnMut(usize) -> Result<(String, String), AppSW>,
+) -> Result<bool, AppSW> {
     let total_pages = (total_fields + FIELDS_PER_PAGE - 1) / FIELDS_PER_PAGE;
-    
+
     for page in 0..total_pages {
         let start_idx = page * FIELDS_PER_PAGE;
         let end_idx = ((page + 1) * FIELDS_PER_PAGE).min(total_fields);
-        
-        let mut page_fields = Vec::with_capacity(end_idx - start_idx + 1);
-        
+
+        let mut page_fields = Vec::with_capacity(end_idx - start_idx);
         for i in start_idx..end_idx {
-            let field = build_field_at_index(preview, i)?;
-            page_fields.push(field);
+            page_fields.push(field_at(i)?);
         }
-        
-        let field_refs: Vec<Field> = page_fields.iter()
+
+        let field_refs: Vec<Field> = page_fields
+            .iter()
             .map(|(name, value)| Field {
                 name: name.as_str(),
                 value: value.as_str(),
             })
             .collect();
-        
-        let action_text = if page == total_pages - 1 { "Sign" } else { "Next" };
-        
+
+        let action_text = if page == total_pages - 1 { final_action } else { "Next" };
+
         let subtitle = format!("Section {}/{}", page + 1, total_pages);
         let review = NbglReview::new()
-            .titles("Review Transaction", &subtitle, action_text)
+            .titles(title, &subtitle, action_text)
             .light();
-        
-        let approved = review.show(&field_refs);
-        
-        if !approved {
+
+        if !review.show(&field_refs) {
             return Ok(false);
         }
     }
-    
+
     Ok(true)
 }
 
+/// Memo-level preview for a MultiSig wallet setup/co-sign (`TX_MULTISIG`):
+/// shows the "M of N" threshold, then scrolls through each participant's
+/// address (not a raw hex key, same formatting `ui_display_pk` uses) so the
+/// device is useful for setting up or co-signing a multisig wallet instead of
+/// silently treating the zero-output memo as a no-op.
+pub fn ui_display_memo_multisig() -> Result<bool, AppSW> {
+    let ws = memo_ws_mut();
+    let multisig = ws.multisig.as_ref().ok_or(AppSW::MemoInvalid)?;
+    let total_fields = 1 + multisig.participants.len();
+
+    show_paginated_review("MultiSig", "Continue", total_fields, |index| {
+        let ws = memo_ws_mut();
+        let multisig = ws.multisig.as_ref().ok_or(AppSW::MemoInvalid)?;
+
+        if index == 0 {
+            return Ok((
+                "Threshold".to_string(),
+                format!("{} of {}", multisig.threshold, multisig.participants.len()),
+            ));
+        }
+
+        let participant_idx = index - 1;
+        if participant_idx < multisig.participants.len() {
+            let addr = format_address_safe(&multisig.participants[participant_idx], true, false, true);
+            return Ok((format!("Participant {}", participant_idx + 1), addr));
+        }
+
+        Err(AppSW::TxDisplayFail)
+    })
+}
+
+/// Memo-level preview for a Contract Call (`TX_INVOKE_CONTRACT`): shows the
+/// target contract, entrypoint selector, each deposit the host claims to
+/// attach, and its bounded parameter preview - approved here before the tx
+/// body streams in. The streamed body's own contract, entrypoint, deposits
+/// and parameter digest are shown separately by `ui_display_contract_call`;
+/// they are not compared against this memo preview.
+fn ui_display_memo_invoke() -> Result<bool, AppSW> {
+    let ws = memo_ws_mut();
+    let invoke = ws.invoke.as_ref().ok_or(AppSW::MemoInvalid)?;
+    let total_fields = 3 + invoke.deposits.len();
+
+    show_paginated_review("Contract Call", "Continue", total_fields, |index| {
+        let ws = memo_ws_mut();
+        let invoke = ws.invoke.as_ref().ok_or(AppSW::MemoInvalid)?;
+
+        if index == 0 {
+            return Ok(("Contract".to_string(), to_hex_string(&invoke.contract)));
+        }
+        if index == 1 {
+            return Ok(("Entrypoint".to_string(), invoke.entrypoint.to_string()));
+        }
+
+        let deposit_start = 2;
+        let deposit_end = deposit_start + invoke.deposits.len();
+        if index >= deposit_start && index < deposit_end {
+            let deposit = &invoke.deposits[index - deposit_start];
+            let label = format!("Deposit {}", index - deposit_start + 1);
+            let value = format!(
+                "{}\n{}",
+                format_asset_from_index(deposit.asset_index),
+                format_memo_amount(deposit.asset_index, deposit.amount)
+            );
+            return Ok((label, value));
+        }
+
+        if index == deposit_end {
+            let text = String::from_utf8_lossy(&invoke.params_preview).into_owned();
+            return Ok(("Params".to_string(), text));
+        }
+
+        Err(AppSW::TxDisplayFail)
+    })
+}
+
+/// Memo-level preview for a Deploy Contract (`TX_DEPLOY_CONTRACT`): shows the
+/// declared bytecode size/commitment and any constructor deposits.
+fn ui_display_memo_deploy() -> Result<bool, AppSW> {
+    let ws = memo_ws_mut();
+    let deploy = ws.deploy.as_ref().ok_or(AppSW::MemoInvalid)?;
+    let total_fields = 1 + deploy.deposits.len();
+
+    show_paginated_review("Deploy Contract", "Continue", total_fields, |index| {
+        let ws = memo_ws_mut();
+        let deploy = ws.deploy.as_ref().ok_or(AppSW::MemoInvalid)?;
+
+        if index == 0 {
+            return Ok((
+                "Module".to_string(),
+                format!("{} bytes\n{}", deploy.module_size, to_hex_string(&deploy.module_commitment)),
+            ));
+        }
+
+        let deposit_start = 1;
+        let deposit_end = deposit_start + deploy.deposits.len();
+        if index >= deposit_start && index < deposit_end {
+            let deposit = &deploy.deposits[index - deposit_start];
+            let label = format!("Ctor deposit {}", index - deposit_start + 1);
+            let value = format!(
+                "{}\n{}",
+                format_asset_from_index(deposit.asset_index),
+                format_memo_amount(deposit.asset_index, deposit.amount)
+            );
+            return Ok((label, value));
+        }
+
+        Err(AppSW::TxDisplayFail)
+    })
+}
+
 fn build_field_at_index(preview: &MemoPreview, index: usize) -> Result<(String, String), AppSW> {
     let ws = memo_ws_mut();
     
@@ -72,14 +202,14 @@ fn build_field_at_index(preview: &MemoPreview, index: usize) -> Result<(String,
         let label = format!("Output {}", out_idx + 1);
         let addr = format_address_safe(&out.dest, true, true, true);
         let asset = format_asset_from_index(out.asset_index);
-        let amt = format_amount(out.amount);
-        
+        let amt = format_memo_amount(out.asset_index, out.amount);
+
         let value = format!("{addr}\n{asset}\n{amt}");
         return Ok((label, value));
     }
-    
+
     if index == output_end {
-        return Ok(("Fee".to_string(), format_amount(preview.fee)));
+        return Ok(("Fee".to_string(), format_memo_amount(NATIVE_ASSET_INDEX, preview.fee)));
     }
     
     if index == output_end + 1 {
@@ -89,6 +219,161 @@ fn build_field_at_index(preview: &MemoPreview, index: usize) -> Result<(String,
     Err(AppSW::TxDisplayFail)
 }
 
+/// Approval screen for `handler_sign_message`: shows the domain-separated
+/// digest of the message the device is about to sign.
+pub fn ui_display_message_hash(hash: &[u8; 64]) -> Result<bool, AppSW> {
+    let digest_hex = to_hex_string(hash);
+    let field = Field {
+        name: "Message hash",
+        value: digest_hex.as_str(),
+    };
+
+    let review = NbglReview::new()
+        .titles("Sign Message", "Confirm message signature", "Sign")
+        .light();
+
+    Ok(review.show(&[field]))
+}
+
+/// Approval screen for a transfer's decrypted `extra_data` memo, shown once
+/// its Poly1305 tag has verified during tx-body streaming - i.e. after the
+/// initial transaction preview (`ui_display_memo_tx`) has already been
+/// approved, since the ciphertext itself only arrives with the tx body.
+pub fn ui_display_extra_data(plaintext: &[u8]) -> Result<bool, AppSW> {
+    let text = String::from_utf8_lossy(plaintext).into_owned();
+    let field = Field {
+        name: "Extra data",
+        value: text.as_str(),
+    };
+
+    let review = NbglReview::new()
+        .titles("Transfer Memo", "Confirm decrypted memo", "Continue")
+        .light();
+
+    Ok(review.show(&[field]))
+}
+
+/// Approval screen for a MultiSig transaction: shows the signing threshold
+/// and the full participant set before the device signs.
+pub fn ui_display_multisig(threshold: u8, participants: &[[u8; 32]]) -> Result<bool, AppSW> {
+    let mut fields = Vec::with_capacity(1 + participants.len());
+    fields.push((
+        String::from("Threshold"),
+        format!("{} of {}", threshold, participants.len()),
+    ));
+    for (i, participant) in participants.iter().enumerate() {
+        fields.push((format!("Participant {}", i + 1), to_hex_string(participant)));
+    }
+
+    let field_refs: Vec<Field> = fields
+        .iter()
+        .map(|(name, value)| Field {
+            name: name.as_str(),
+            value: value.as_str(),
+        })
+        .collect();
+
+    let review = NbglReview::new()
+        .titles("MultiSig", "Confirm participant set", "Sign")
+        .light();
+
+    Ok(review.show(&field_refs))
+}
+
+/// Approval screen for this device's cosigning turn in a MultiSig partial
+/// signature round: shows how many signatures have been collected so far
+/// against the threshold, so the user can see whether their signature is the
+/// one completing the set before it is produced.
+pub fn ui_display_multisig_partial_sign(
+    signer_index: u8,
+    threshold: u8,
+    partial_count: u8,
+) -> Result<bool, AppSW> {
+    let signer_index_str = signer_index.to_string();
+    let collected_str = format!("{} of {}", partial_count, threshold);
+
+    let fields = [
+        Field {
+            name: "Your signer index",
+            value: signer_index_str.as_str(),
+        },
+        Field {
+            name: "Signatures collected",
+            value: collected_str.as_str(),
+        },
+    ];
+
+    let review = NbglReview::new()
+        .titles("MultiSig Cosign", "Confirm signing round", "Sign")
+        .light();
+
+    Ok(review.show(&fields))
+}
+
+/// Approval screen for a Contract Call transaction: shows the target
+/// contract, entrypoint, declared deposits, and a digest of the parameter
+/// blob (the raw parameters are streamed straight into a hash, never
+/// buffered for display).
+pub fn ui_display_contract_call(
+    contract: &[u8; 32],
+    entrypoint: u64,
+    deposits: &[([u8; 32], u64)],
+    params_hash: &[u8; 64],
+) -> Result<bool, AppSW> {
+    let mut fields = Vec::with_capacity(3 + deposits.len());
+    fields.push((String::from("Contract"), to_hex_string(contract)));
+    fields.push((String::from("Entrypoint"), entrypoint.to_string()));
+    for (i, (asset, amount)) in deposits.iter().enumerate() {
+        let value = format!("{}\n{}", to_hex_string(asset), amount);
+        fields.push((format!("Deposit {}", i + 1), value));
+    }
+    fields.push((String::from("Params hash"), to_hex_string(params_hash)));
+
+    let field_refs: Vec<Field> = fields
+        .iter()
+        .map(|(name, value)| Field {
+            name: name.as_str(),
+            value: value.as_str(),
+        })
+        .collect();
+
+    let review = NbglReview::new()
+        .titles("Contract Call", "Confirm contract invocation", "Sign")
+        .light();
+
+    Ok(review.show(&field_refs))
+}
+
+/// Approval screen for a Deploy Contract transaction: shows the deployed
+/// module and any constructor deposits.
+pub fn ui_display_deploy_contract(
+    module_hash: &[u8; 32],
+    module_size: u64,
+    deposits: &[([u8; 32], u64)],
+) -> Result<bool, AppSW> {
+    let mut fields = Vec::with_capacity(2 + deposits.len());
+    fields.push((String::from("Module hash"), to_hex_string(module_hash)));
+    fields.push((String::from("Module size"), format!("{module_size} bytes")));
+    for (i, (asset, amount)) in deposits.iter().enumerate() {
+        let value = format!("{}\n{}", to_hex_string(asset), amount);
+        fields.push((format!("Ctor deposit {}", i + 1), value));
+    }
+
+    let field_refs: Vec<Field> = fields
+        .iter()
+        .map(|(name, value)| Field {
+            name: name.as_str(),
+            value: value.as_str(),
+        })
+        .collect();
+
+    let review = NbglReview::new()
+        .titles("Deploy Contract", "Confirm module deployment", "Sign")
+        .light();
+
+    Ok(review.show(&field_refs))
+}
+
 fn tx_type_name(tx_type: u8) -> &'static str {
     match tx_type {
         TX_TRANSFER => "Transfer",
@@ -113,9 +398,17 @@ fn format_asset_from_index(index: u8) -> String {
     }
 }
 
-fn format_amount(amount: u64) -> String {
-    // XELIS uses 8 decimals
-    let major = amount / 100_000_000;
-    let minor = amount % 100_000_000;
-    format!("{}.{:08}", major, minor)
+/// Render a transfer/fee amount using the decimals and ticker of its asset,
+/// e.g. `12.5 XEL` instead of the raw atomic `1250000000`.
+fn format_memo_amount(asset_index: u8, amount: u64) -> String {
+    let (decimals, ticker) = get_memo_asset_display(asset_index);
+    let ticker = String::from_utf8_lossy(&ticker).into_owned();
+
+    // Small Nano screens are tight on space; drop trailing fractional zeros there.
+    #[cfg(any(target_os = "nanos", target_os = "nanosplus", target_os = "nanox"))]
+    let amount_str = format_fixed_point_trimmed(amount, decimals);
+    #[cfg(not(any(target_os = "nanos", target_os = "nanosplus", target_os = "nanox")))]
+    let amount_str = format_fixed_point(amount, decimals);
+
+    format!("{amount_str} {ticker}")
 }
\ No newline at end of file
@@ -10,13 +10,27 @@ const SEPARATOR: u8 = b':';
 
 use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
-// Maximum address length: prefix(3) + separator(1) + data(~52) + checksum(6) = ~62
-const MAX_ADDRESS_LEN: usize = 72;
+const ADDRESS_TYPE_NORMAL: u8 = 0x00;
+const ADDRESS_TYPE_INTEGRATED: u8 = 0x01;
+
+/// Largest integrated-address payload (payment ID / structured data) this
+/// app will encode or decode.
+pub const MAX_INTEGRATED_DATA_LEN: usize = 32;
+
+// Largest pre-bech32 payload: compressed key(32) + type(1) + len(1) + the
+// largest integrated-data payload.
+const MAX_DECODED_LEN: usize = 32 + 1 + 1 + MAX_INTEGRATED_DATA_LEN;
+
+// Maximum address length: prefix(3) + separator(1) + data+checksum for the
+// largest (integrated) payload, with a little headroom.
+const MAX_ADDRESS_LEN: usize = 128;
 
 pub struct Address {
     mainnet: bool,
     public_key: XelisPublicKey,
+    integrated_data: Option<Vec<u8>>,
 }
 
 impl Address {
@@ -24,6 +38,96 @@ impl Address {
         Self {
             mainnet,
             public_key,
+            integrated_data: None,
+        }
+    }
+
+    /// Same as [`Address::new`], but embeds a payment-ID/structured-data
+    /// payload so the encoded address carries `AddressType::Integrated`
+    /// instead of `AddressType::Normal`.
+    pub fn new_integrated(mainnet: bool, public_key: XelisPublicKey, integrated_data: &[u8]) -> Self {
+        Self {
+            mainnet,
+            public_key,
+            integrated_data: Some(integrated_data.to_vec()),
+        }
+    }
+
+    pub fn integrated_data(&self) -> Option<&[u8]> {
+        self.integrated_data.as_deref()
+    }
+
+    /// Inverse of [`Address::to_bytes`]: parses an ASCII `xel:`/`xet:`
+    /// address, verifies its bech32 checksum, and reconstructs the
+    /// compressed public key it encodes. Lets the device double-check that
+    /// a recipient address supplied by the host actually decodes to the
+    /// key it is about to display, instead of trusting the string as-is.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AppSW> {
+        let s = core::str::from_utf8(bytes).map_err(|_| AppSW::AddressError)?;
+        Self::from_str(s)
+    }
+
+    /// Same as [`Address::from_bytes`] but takes an already-decoded `&str`.
+    pub fn from_str(s: &str) -> Result<Self, AppSW> {
+        let colon = s.find(':').ok_or(AppSW::AddressError)?;
+        let hrp = &s[..colon];
+        let data_part = &s[colon + 1..];
+
+        let mainnet = match hrp {
+            PREFIX_ADDRESS => true,
+            TESTNET_PREFIX_ADDRESS => false,
+            _ => return Err(AppSW::AddressError),
+        };
+
+        // Need at least the 6-character checksum.
+        if data_part.len() <= 6 || data_part.len() > MAX_ADDRESS_LEN {
+            return Err(AppSW::AddressError);
+        }
+
+        let mut values = [0u8; MAX_ADDRESS_LEN];
+        for (i, &b) in data_part.as_bytes().iter().enumerate() {
+            values[i] = charset_value(b)?;
+        }
+        let values = &values[..data_part.len()];
+
+        if !verify_checksum(hrp, values) {
+            return Err(AppSW::AddressError);
+        }
+
+        let data_values = &values[..values.len() - 6];
+
+        let mut decoded = [0u8; MAX_DECODED_LEN];
+        let decoded_len = convert_bits_fixed(data_values, 5, 8, false, &mut decoded)?;
+        if decoded_len < 33 {
+            return Err(AppSW::AddressError);
+        }
+
+        // decoded[..32] is the LE-compressed key, decoded[32] the
+        // AddressType byte - split it off and flip back to the internal
+        // BE representation.
+        let mut pk_le = [0u8; 32];
+        pk_le.copy_from_slice(&decoded[..32]);
+
+        let xpk = XelisPublicKey::new(CompressedRistretto::from_le_bytes(pk_le));
+
+        match decoded[32] {
+            ADDRESS_TYPE_NORMAL => {
+                if decoded_len != 33 {
+                    return Err(AppSW::AddressError);
+                }
+                Ok(Self::new(mainnet, xpk))
+            }
+            ADDRESS_TYPE_INTEGRATED => {
+                if decoded_len < 34 {
+                    return Err(AppSW::AddressError);
+                }
+                let data_len = decoded[33] as usize;
+                if decoded_len != 34 + data_len || data_len > MAX_INTEGRATED_DATA_LEN {
+                    return Err(AppSW::AddressError);
+                }
+                Ok(Self::new_integrated(mainnet, xpk, &decoded[34..34 + data_len]))
+            }
+            _ => Err(AppSW::AddressError),
         }
     }
 
@@ -48,13 +152,29 @@ impl Address {
 
         // Get compressed public key (BE) and convert to LE for Xelis
         let le_bytes = self.public_key.compressed.to_le_bytes(); // This converts BE to LE
-        let mut data_to_encode = [0u8; 33];
+        let mut data_to_encode = [0u8; MAX_DECODED_LEN];
         data_to_encode[..32].copy_from_slice(&le_bytes);
-        data_to_encode[32] = 0x00; // AddressType::Normal
 
-        // Convert public key to 5-bit groups using LE bytes
-        let mut bits_buf = [0u8; 64];
-        let bits_len = convert_bits_fixed(&data_to_encode, 8, 5, true, &mut bits_buf)?;
+        let data_len = match &self.integrated_data {
+            None => {
+                data_to_encode[32] = ADDRESS_TYPE_NORMAL;
+                33
+            }
+            Some(integrated_data) => {
+                if integrated_data.len() > MAX_INTEGRATED_DATA_LEN {
+                    return Err(AppSW::AddressError);
+                }
+                data_to_encode[32] = ADDRESS_TYPE_INTEGRATED;
+                data_to_encode[33] = integrated_data.len() as u8;
+                data_to_encode[34..34 + integrated_data.len()].copy_from_slice(integrated_data);
+                34 + integrated_data.len()
+            }
+        };
+
+        // Convert public key (+ optional integrated data) to 5-bit groups
+        // using LE bytes.
+        let mut bits_buf = [0u8; MAX_DECODED_LEN * 8 / 5 + 1];
+        let bits_len = convert_bits_fixed(&data_to_encode[..data_len], 8, 5, true, &mut bits_buf)?;
 
         // Calculate checksum
         let checksum = create_checksum_fixed(prefix, &bits_buf[..bits_len]);
@@ -154,6 +274,40 @@ pub fn create_checksum_fixed(hrp: &str, data: &[u8]) -> [u8; 6] {
     result
 }
 
+/// Inverse of [`CHARSET`]: maps a bech32 data character back to its 5-bit
+/// value, rejecting anything outside the charset instead of silently
+/// treating it as zero.
+fn charset_value(byte: u8) -> Result<u8, AppSW> {
+    CHARSET
+        .iter()
+        .position(|&c| c == byte)
+        .map(|pos| pos as u8)
+        .ok_or(AppSW::AddressError)
+}
+
+/// Checks a bech32 string's checksum: runs `polymod_step` over the
+/// HRP-expanded values followed by the data and checksum values, and
+/// requires the final residue to equal the bech32 valid-checksum constant.
+fn verify_checksum(hrp: &str, data_and_checksum: &[u8]) -> bool {
+    let mut chk = 1u32;
+
+    for &b in hrp.as_bytes() {
+        chk = polymod_step(chk, b >> 5);
+    }
+
+    chk = polymod_step(chk, 0);
+
+    for &b in hrp.as_bytes() {
+        chk = polymod_step(chk, b & 31);
+    }
+
+    for &b in data_and_checksum {
+        chk = polymod_step(chk, b);
+    }
+
+    chk == 1
+}
+
 #[inline]
 fn polymod_step(chk: u32, value: u8) -> u32 {
     const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
@@ -0,0 +1,408 @@
+//! Streaming verifier for the aggregated Bulletproof range proof that XELIS
+//! attaches to confidential transfer outputs, proving every committed
+//! amount lies in `[0, 2^64)`. Fields arrive in wire order (`A, S, T1, T2,
+//! tau_x, mu, t_hat`, then `log2(n*m)` rounds of `(L_i, R_i)`, then the
+//! final `a, b`); the Fiat-Shamir transcript (`y, z, x, u_i`) is folded in
+//! as soon as the bytes it depends on have arrived, so nothing needs to be
+//! re-read once streaming finishes. The per-index generators `g_i`/`h_i`
+//! are derived lazily, one at a time, instead of materializing the full
+//! `n*m` vector (which can run into the hundreds for a multi-output tx).
+
+use crate::crypto::{ristretto::*, scalar::*, sha::sha3_512, *};
+use crate::AppSW;
+use alloc::vec::Vec;
+
+/// Bit-width of the range each committed amount is proven to lie within.
+pub const RANGE_BITS: usize = 64;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    A,
+    S,
+    T1,
+    T2,
+    TauX,
+    Mu,
+    THat,
+    IppL,
+    IppR,
+    AScalar,
+    BScalar,
+    Done,
+}
+
+pub struct BulletproofVerifier {
+    /// Proof width, padded up to a power of two.
+    m: usize,
+    /// log2(m * RANGE_BITS) inner-product rounds.
+    rounds: usize,
+    /// V_j: the per-output Pedersen commitments this proof covers.
+    commitments: Vec<CompressedRistretto>,
+
+    a: Option<CompressedRistretto>,
+    s_commit: Option<CompressedRistretto>,
+    t1: Option<CompressedRistretto>,
+    t2: Option<CompressedRistretto>,
+    tau_x: Option<[u8; 32]>,
+    mu: Option<[u8; 32]>,
+    t_hat: Option<[u8; 32]>,
+
+    ipp_l: Vec<CompressedRistretto>,
+    ipp_r: Vec<CompressedRistretto>,
+    challenges: Vec<[u8; 32]>,
+
+    a_scalar: Option<[u8; 32]>,
+    b_scalar: Option<[u8; 32]>,
+
+    y: Option<[u8; 32]>,
+    z: Option<[u8; 32]>,
+    x: Option<[u8; 32]>,
+
+    field: Field,
+    partial: [u8; 32],
+    partial_len: usize,
+}
+
+impl BulletproofVerifier {
+    /// `commitments` are the already-verified per-output Pedersen
+    /// commitments (LE, as received on the wire); the proof width is padded
+    /// up to the next power of two, with the padding slots contributing
+    /// nothing to the verification equations.
+    pub fn new(commitments: Vec<[u8; 32]>) -> Self {
+        let m = commitments.len().max(1).next_power_of_two();
+        let rounds = (m * RANGE_BITS).trailing_zeros() as usize;
+        Self {
+            m,
+            rounds,
+            commitments: commitments
+                .into_iter()
+                .map(CompressedRistretto::from_le_bytes)
+                .collect(),
+            a: None,
+            s_commit: None,
+            t1: None,
+            t2: None,
+            tau_x: None,
+            mu: None,
+            t_hat: None,
+            ipp_l: Vec::with_capacity(rounds),
+            ipp_r: Vec::with_capacity(rounds),
+            challenges: Vec::with_capacity(rounds),
+            a_scalar: None,
+            b_scalar: None,
+            y: None,
+            z: None,
+            x: None,
+            field: Field::A,
+            partial: [0u8; 32],
+            partial_len: 0,
+        }
+    }
+
+    /// Feed one byte of the streamed proof. Returns `Ok(true)` once the
+    /// final byte has been consumed and the whole proof has verified;
+    /// fails closed with [`AppSW::InvalidCommitment`] on any mismatch.
+    pub fn feed(&mut self, byte: u8) -> Result<bool, AppSW> {
+        if self.field == Field::Done {
+            return Err(AppSW::TxParsingFail);
+        }
+
+        self.partial[self.partial_len] = byte;
+        self.partial_len += 1;
+        if self.partial_len < 32 {
+            return Ok(false);
+        }
+
+        let chunk: [u8; 32] = self.partial;
+        self.partial_len = 0;
+
+        match self.field {
+            Field::A => {
+                self.a = Some(CompressedRistretto::from_le_bytes(chunk));
+                self.field = Field::S;
+            }
+            Field::S => {
+                self.s_commit = Some(CompressedRistretto::from_le_bytes(chunk));
+                // y and z only depend on A and S - fold them in as soon as
+                // both have arrived rather than waiting for the rest.
+                let a = self.a.unwrap().to_be_bytes();
+                let s = self.s_commit.unwrap().to_be_bytes();
+                let y = hash_to_scalar(&[&a, &s])?;
+                self.z = Some(hash_to_scalar(&[&y])?);
+                self.y = Some(y);
+                self.field = Field::T1;
+            }
+            Field::T1 => {
+                self.t1 = Some(CompressedRistretto::from_le_bytes(chunk));
+                self.field = Field::T2;
+            }
+            Field::T2 => {
+                self.t2 = Some(CompressedRistretto::from_le_bytes(chunk));
+                let t1 = self.t1.unwrap().to_be_bytes();
+                let t2 = self.t2.unwrap().to_be_bytes();
+                self.x = Some(hash_to_scalar(&[&t1, &t2])?);
+                self.field = Field::TauX;
+            }
+            Field::TauX => {
+                self.tau_x = Some(le_to_be(chunk));
+                self.field = Field::Mu;
+            }
+            Field::Mu => {
+                self.mu = Some(le_to_be(chunk));
+                self.field = Field::THat;
+            }
+            Field::THat => {
+                self.t_hat = Some(le_to_be(chunk));
+                self.verify_polynomial_identity()?;
+                self.field = Field::IppL;
+            }
+            Field::IppL => {
+                self.ipp_l.push(CompressedRistretto::from_le_bytes(chunk));
+                self.field = Field::IppR;
+            }
+            Field::IppR => {
+                self.ipp_r.push(CompressedRistretto::from_le_bytes(chunk));
+                let l = self.ipp_l.last().unwrap().to_be_bytes();
+                let r = self.ipp_r.last().unwrap().to_be_bytes();
+                self.challenges.push(hash_to_scalar(&[&l, &r])?);
+                self.field = if self.ipp_l.len() == self.rounds {
+                    Field::AScalar
+                } else {
+                    Field::IppL
+                };
+            }
+            Field::AScalar => {
+                self.a_scalar = Some(le_to_be(chunk));
+                self.field = Field::BScalar;
+            }
+            Field::BScalar => {
+                self.b_scalar = Some(le_to_be(chunk));
+                self.verify_inner_product()?;
+                self.field = Field::Done;
+                return Ok(true);
+            }
+            Field::Done => unreachable!(),
+        }
+
+        Ok(false)
+    }
+
+    /// Checks `t_hat*G + tau_x*H == sum_j z^(2+j)*V_j + delta(y,z)*G + x*T1 + x^2*T2`.
+    fn verify_polynomial_identity(&self) -> Result<(), AppSW> {
+        let y = self.y.ok_or(AppSW::TxParsingFail)?;
+        let z = self.z.ok_or(AppSW::TxParsingFail)?;
+        let x = self.x.ok_or(AppSW::TxParsingFail)?;
+        let tau_x = self.tau_x.ok_or(AppSW::TxParsingFail)?;
+        let t_hat = self.t_hat.ok_or(AppSW::TxParsingFail)?;
+        let t1 = self.t1.ok_or(AppSW::TxParsingFail)?.decompress()?;
+        let t2 = self.t2.ok_or(AppSW::TxParsingFail)?.decompress()?;
+
+        let delta = self.delta(&y, &z)?;
+
+        let lhs = edwards_add(
+            &scalar_mult_ristretto(&t_hat, &XELIS_G_POINT)?,
+            &scalar_mult_ristretto(&tau_x, &XELIS_H_POINT)?,
+        )?;
+
+        let mut rhs = scalar_mult_ristretto(&delta, &XELIS_G_POINT)?;
+        rhs = edwards_add(&rhs, &scalar_mult_ristretto(&x, &t1)?)?;
+        let x2 = scalar_sq(&x)?;
+        rhs = edwards_add(&rhs, &scalar_mult_ristretto(&x2, &t2)?)?;
+
+        // Padding slots (value 0, blinder 0) would decompress to the
+        // identity and contribute nothing, so only the real V_j are folded in.
+        let mut z_pow = scalar_sq(&z)?; // z^2
+        for v_comp in &self.commitments {
+            let v = v_comp.decompress()?;
+            rhs = edwards_add(&rhs, &scalar_mult_ristretto(&z_pow, &v)?)?;
+            z_pow = scalar_mul(&z_pow, &z)?;
+        }
+
+        if lhs.compress()?.to_be_bytes() != rhs.compress()?.to_be_bytes() {
+            return Err(AppSW::InvalidCommitment);
+        }
+        Ok(())
+    }
+
+    /// delta(y,z) = (z - z^2)*<1^nm, y^nm> - sum_j z^(3+j)*<1^n, 2^n>
+    ///
+    /// The second term's `z` power starts one higher than the `z^(2+j)*V_j`
+    /// sum in `verify_polynomial_identity` - it comes from `z^3*<1^n,2^n>`
+    /// folded in per output, not `z^2*<1^n,2^n>` - so `z_pow` below seeds at
+    /// `z^3`, not `z^2`.
+    fn delta(&self, y: &[u8; 32], z: &[u8; 32]) -> Result<[u8; 32], AppSW> {
+        let nm = self.m * RANGE_BITS;
+
+        let mut sum_y = scalar_zero();
+        let mut y_pow = scalar_one();
+        for _ in 0..nm {
+            sum_y = scalar_add_new(&sum_y, &y_pow)?;
+            y_pow = scalar_mul(&y_pow, y)?;
+        }
+
+        let z2 = scalar_sq(z)?;
+        let z_minus_z2 = scalar_sub_new(z, &z2)?;
+        let term1 = scalar_mul(&z_minus_z2, &sum_y)?;
+
+        let range_max = range_max_scalar();
+        let mut z_pow = scalar_mul(&z2, z)?; // z^3
+        let mut term2 = scalar_zero();
+        for _ in 0..self.m {
+            term2 = scalar_add_new(&term2, &scalar_mul(&z_pow, &range_max)?)?;
+            z_pow = scalar_mul(&z_pow, z)?;
+        }
+
+        scalar_sub_new(&term1, &term2)
+    }
+
+    /// Folds the IPP rounds into the accumulator and the per-index scalar
+    /// `s_i = prod u_k^{+-1}`, then checks the whole inner-product relation
+    /// with a single multi-exponentiation against the lazily-derived
+    /// generators `g_i`/`h_i`.
+    fn verify_inner_product(&self) -> Result<(), AppSW> {
+        let mu = self.mu.ok_or(AppSW::TxParsingFail)?;
+        let a_scalar = self.a_scalar.ok_or(AppSW::TxParsingFail)?;
+        let b_scalar = self.b_scalar.ok_or(AppSW::TxParsingFail)?;
+        let y = self.y.ok_or(AppSW::TxParsingFail)?;
+        let nm = self.m * RANGE_BITS;
+
+        let mut acc = self.a.ok_or(AppSW::TxParsingFail)?.decompress()?;
+        for round in 0..self.rounds {
+            let u2 = scalar_sq(&self.challenges[round])?;
+            let u2_inv = scalar_invert(&u2)?;
+            let l = self.ipp_l[round].decompress()?;
+            let r = self.ipp_r[round].decompress()?;
+            acc = edwards_add(&acc, &scalar_mult_ristretto(&u2, &l)?)?;
+            acc = edwards_add(&acc, &scalar_mult_ristretto(&u2_inv, &r)?)?;
+        }
+
+        let mut check = acc;
+        let mut y_pow = scalar_one();
+        for i in 0..nm {
+            let mut s_i = scalar_one();
+            for round in 0..self.rounds {
+                let bit = (i >> (self.rounds - 1 - round)) & 1;
+                let u = self.challenges[round];
+                let factor = if bit == 1 { u } else { scalar_invert(&u)? };
+                s_i = scalar_mul(&s_i, &factor)?;
+            }
+
+            let g_i = generator(b"bp.g", i)?;
+            let h_i = generator(b"bp.h", i)?;
+
+            // Fold the y-weighting into h_i directly: h_i' = y^-i * h_i.
+            let y_inv_pow = scalar_invert(&y_pow)?;
+            let h_exp = scalar_mult_ristretto(&y_inv_pow, &h_i)?;
+
+            let a_si = scalar_mul(&a_scalar, &s_i)?;
+            let s_i_inv = scalar_invert(&s_i)?;
+            let neg_b_over_si = scalar_negate(&scalar_mul(&b_scalar, &s_i_inv)?)?;
+
+            check = edwards_add(&check, &scalar_mult_ristretto(&a_si, &g_i)?)?;
+            check = edwards_add(&check, &scalar_mult_ristretto(&neg_b_over_si, &h_exp)?)?;
+
+            y_pow = scalar_mul(&y_pow, &y)?;
+        }
+
+        // Ties the IPP accumulator back to the mu/t_hat blinding.
+        let ab = scalar_mul(&a_scalar, &b_scalar)?;
+        let rhs = edwards_add(
+            &scalar_mult_ristretto(&mu, &XELIS_H_POINT)?,
+            &scalar_mult_ristretto(&ab, &XELIS_G_POINT)?,
+        )?;
+
+        if check.compress()?.to_be_bytes() != rhs.compress()?.to_be_bytes() {
+            return Err(AppSW::InvalidCommitment);
+        }
+        Ok(())
+    }
+}
+
+fn hash_to_scalar(parts: &[&[u8]]) -> Result<[u8; 32], AppSW> {
+    let mut buf = Vec::new();
+    for p in parts {
+        buf.extend_from_slice(p);
+    }
+    let wide = sha3_512(&buf)?;
+    scalar_from_bytes_wide(&wide)
+}
+
+/// Upper bound on try-and-increment attempts in [`generator`]. Each attempt
+/// succeeds with probability ~1/2, so this is astronomically generous
+/// headroom, not a realistic ceiling.
+const GENERATOR_MAX_ATTEMPTS: u32 = 256;
+
+/// Derive the i-th generator lazily via hash-to-point (try-and-increment):
+/// hash `label || index || counter`, and take the first counter whose digest
+/// decodes as a valid compressed Ristretto point. This is a genuine
+/// nothing-up-my-sleeve construction - unlike `hash_to_scalar(..) * H`, the
+/// resulting point has no known discrete log relative to `G`/`H` or to any
+/// other generator, which is what the Bulletproof soundness proof actually
+/// requires. Never materializes the full n*m generator vector.
+fn generator(label: &[u8], index: usize) -> Result<RistrettoPoint, AppSW> {
+    for counter in 0u32..GENERATOR_MAX_ATTEMPTS {
+        let mut buf = Vec::with_capacity(label.len() + 8 + 4);
+        buf.extend_from_slice(label);
+        buf.extend_from_slice(&(index as u64).to_le_bytes());
+        buf.extend_from_slice(&counter.to_le_bytes());
+
+        let wide = sha3_512(&buf)?;
+        let mut candidate = [0u8; 32];
+        candidate.copy_from_slice(&wide[..32]);
+
+        if let Ok(point) = CompressedRistretto::from_be_bytes(candidate).decompress() {
+            return Ok(point);
+        }
+    }
+    Err(AppSW::CryptoError)
+}
+
+fn le_to_be(mut bytes: [u8; 32]) -> [u8; 32] {
+    bytes.reverse();
+    bytes
+}
+
+fn scalar_zero() -> [u8; 32] {
+    [0u8; 32]
+}
+
+fn scalar_one() -> [u8; 32] {
+    let mut s = [0u8; 32];
+    s[31] = 1;
+    s
+}
+
+/// 2^RANGE_BITS - 1 as a 32-byte big-endian scalar (fits in the low 8 bytes).
+fn range_max_scalar() -> [u8; 32] {
+    let mut s = [0u8; 32];
+    for b in s[32 - RANGE_BITS / 8..].iter_mut() {
+        *b = 0xff;
+    }
+    s
+}
+
+fn scalar_add_new(a: &[u8; 32], b: &[u8; 32]) -> Result<[u8; 32], AppSW> {
+    let mut out = [0u8; 32];
+    scalar_add(&mut out, a, b).map_err(|_| AppSW::CryptoError)?;
+    Ok(out)
+}
+
+fn scalar_sub_new(a: &[u8; 32], b: &[u8; 32]) -> Result<[u8; 32], AppSW> {
+    let mut out = [0u8; 32];
+    scalar_subtract(&mut out, a, b).map_err(|_| AppSW::CryptoError)?;
+    Ok(out)
+}
+
+fn scalar_mul(a: &[u8; 32], b: &[u8; 32]) -> Result<[u8; 32], AppSW> {
+    let mut out = [0u8; 32];
+    scalar_multiply(&mut out, a, b).map_err(|_| AppSW::CryptoError)?;
+    Ok(out)
+}
+
+fn scalar_sq(a: &[u8; 32]) -> Result<[u8; 32], AppSW> {
+    scalar_mul(a, a)
+}
+
+fn scalar_negate(a: &[u8; 32]) -> Result<[u8; 32], AppSW> {
+    scalar_sub_new(&L, a)
+}
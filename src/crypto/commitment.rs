@@ -1,5 +1,5 @@
 use crate::{
-    crypto::{ristretto::*, scalar::*, *},
+    crypto::{bulletproof::BulletproofVerifier, ristretto::*, scalar::*, secure::constant_time_eq, *},
     AppSW,
 };
 use alloc::vec;
@@ -34,11 +34,55 @@ pub fn verify_pedersen_commitment(
     Ok(())
 }
 
+/// Draws a fresh non-zero scalar challenge from the device TRNG for
+/// `verify_commitments_batched` - a zero challenge would drop that output
+/// from the batch entirely, so it's rejected and redrawn.
+fn random_challenge() -> Result<[u8; 32], AppSW> {
+    loop {
+        let mut z = [0u8; 32];
+        scalar_random(&mut z)?;
+        if z.iter().any(|&b| b != 0) {
+            return Ok(z);
+        }
+    }
+}
+
+/// Recompute a transfer's twisted-ElGamal receiver handle `D = blinder · P_receiver`
+/// and compare it against the handle carried in the transaction stream.
+///
+/// A commitment can be well-formed while still pairing it with the wrong
+/// handle, silently burning funds the stated recipient can never decrypt -
+/// this is a distinct check from `verify_pedersen_commitment` and must be
+/// run per output alongside it.
+pub fn verify_receiver_handle(
+    dest_pubkey: &[u8; 32],
+    blinder: &[u8; 32],
+    handle: &[u8; 32],
+) -> Result<(), AppSW> {
+    let receiver_point = CompressedRistretto::from_le_bytes(*dest_pubkey).decompress()?;
+    let expected = scalar_mult_ristretto(blinder, &receiver_point)?;
+    let expected_bytes = expected.compress()?.to_le_bytes();
+
+    if !constant_time_eq(&expected_bytes, handle) {
+        return Err(AppSW::InvalidCommitment);
+    }
+
+    Ok(())
+}
+
 /// State for tracking commitment verification across multiple outputs
 pub struct CommitmentVerifier {
     blinders: Vec<[u8; 32]>,
     outputs_verified: Vec<bool>,
     commitments_verified: usize,
+    commitments: Vec<[u8; 32]>,
+    amounts: Vec<u64>,
+    commitments_batch_verified: bool,
+    handles_verified: Vec<bool>,
+    range_proof: Option<BulletproofVerifier>,
+    range_proof_verified: bool,
+    balance_sum: RistrettoPoint,
+    balance_verified: bool,
 }
 
 impl CommitmentVerifier {
@@ -47,6 +91,14 @@ impl CommitmentVerifier {
             blinders: Vec::new(),
             outputs_verified: Vec::new(),
             commitments_verified: 0,
+            commitments: Vec::new(),
+            amounts: Vec::new(),
+            commitments_batch_verified: false,
+            handles_verified: Vec::new(),
+            range_proof: None,
+            range_proof_verified: false,
+            balance_sum: IDENTITY_POINT,
+            balance_verified: false,
         }
     }
 
@@ -54,6 +106,14 @@ impl CommitmentVerifier {
         self.blinders.clear();
         self.outputs_verified.clear();
         self.commitments_verified = 0;
+        self.commitments.clear();
+        self.amounts.clear();
+        self.commitments_batch_verified = false;
+        self.handles_verified.clear();
+        self.range_proof = None;
+        self.range_proof_verified = false;
+        self.balance_sum = IDENTITY_POINT;
+        self.balance_verified = false;
     }
 
     /// Initialize blinders for a new set (clears existing)
@@ -89,31 +149,188 @@ impl CommitmentVerifier {
     pub fn init_verification(&mut self, output_count: usize) {
         self.outputs_verified = vec![false; output_count];
         self.commitments_verified = 0;
+        self.commitments = vec![[0u8; 32]; output_count];
+        self.amounts = vec![0u64; output_count];
+        self.commitments_batch_verified = false;
+        self.handles_verified = vec![false; output_count];
+        self.range_proof = None;
+        self.range_proof_verified = false;
+        self.balance_sum = IDENTITY_POINT;
+        self.balance_verified = false;
     }
 
-    pub fn verify_output(
+    /// Records output `idx`'s commitment and amount as they stream in.
+    /// Unlike `verify_handle`, this does not check the commitment itself -
+    /// that happens once for every output at once in
+    /// `verify_commitments_batched`, after the whole transfer set is known.
+    pub fn record_output(
         &mut self,
         idx: usize,
         commitment: &[u8; 32],
         amount: u64,
     ) -> Result<(), AppSW> {
-        // Bounds check
         if idx >= self.outputs_verified.len() || idx >= self.blinders.len() {
             return Err(AppSW::TxParsingFail);
         }
 
-        // Verify the commitment
-        verify_pedersen_commitment(commitment, amount, &self.blinders[idx])?;
+        self.commitments[idx] = *commitment;
+        self.amounts[idx] = amount;
+
+        Ok(())
+    }
+
+    /// Verifies every recorded output's Pedersen commitment in a single
+    /// batch using Straus's algorithm: for independent random challenges
+    /// `z_j` (from the device TRNG), accumulates
+    /// `Σ z_j·(amount_j·G + blinder_j·H − C_j)` by running one shared scan
+    /// over 4-bit windows of the `G`/`H` multiples plus one scalar
+    /// multiplication per output against its own commitment point, and
+    /// accepts iff the total is the identity. This replaces one full
+    /// double-and-add per generator per output with a single shared scan,
+    /// and the random `z_j` stops a host from passing with per-output
+    /// errors that cancel each other out across the batch.
+    /// Idempotent: the caller re-enters this once per remaining chunk while
+    /// the witness tail (range proof) keeps streaming in, but the batch
+    /// only needs to run once.
+    pub fn verify_commitments_batched(&mut self) -> Result<(), AppSW> {
+        if self.commitments_batch_verified {
+            return Ok(());
+        }
+
+        let n = self.commitments.len();
+        if n == 0 || n != self.blinders.len() || n != self.amounts.len() {
+            return Err(AppSW::TxParsingFail);
+        }
+
+        let g_table = WindowTable16::build(&XELIS_G_POINT)?;
+        let h_table = WindowTable16::build(&XELIS_H_POINT)?;
+
+        let mut scalars_g = Vec::with_capacity(n);
+        let mut scalars_h = Vec::with_capacity(n);
+        let mut acc = IDENTITY_POINT;
+
+        for idx in 0..n {
+            let z = random_challenge()?;
+
+            let mut amount_scalar = [0u8; 32];
+            amount_scalar[24..32].copy_from_slice(&self.amounts[idx].to_be_bytes());
+
+            let mut zg = [0u8; 32];
+            scalar_multiply(&mut zg, &z, &amount_scalar).map_err(|_| AppSW::CryptoError)?;
+            scalars_g.push(zg);
+
+            let mut zh = [0u8; 32];
+            scalar_multiply(&mut zh, &z, &self.blinders[idx]).map_err(|_| AppSW::CryptoError)?;
+            scalars_h.push(zh);
+
+            let c_point = CompressedRistretto::from_le_bytes(self.commitments[idx]).decompress()?;
+            let neg_c = negate_point(&c_point)?;
+            let zc = scalar_mult_ristretto(&z, &neg_c)?;
+            acc = edwards_add(&acc, &zc)?;
+
+            // Fold this output into the running sum S = Σ C_i for the
+            // balance-conservation check, regardless of how the batch
+            // check below comes out.
+            self.balance_sum = edwards_add(&self.balance_sum, &c_point)?;
+        }
+
+        let batch_point = multi_scalar_mult_g_h(&g_table, &h_table, &scalars_g, &scalars_h)?;
+        acc = edwards_add(&acc, &batch_point)?;
+
+        if acc.compress()?.to_le_bytes() != IDENTITY_POINT.compress()?.to_le_bytes() {
+            return Err(AppSW::InvalidCommitment);
+        }
+
+        for v in self.outputs_verified.iter_mut() {
+            *v = true;
+        }
+        self.commitments_verified = n;
+        self.commitments_batch_verified = true;
+
+        Ok(())
+    }
+
+    /// Recomputes and checks output `idx`'s receiver handle against the
+    /// recipient address from the approved memo. Must be called once per
+    /// output, alongside `verify_commitments_batched`, for `all_verified`
+    /// to pass.
+    pub fn verify_handle(
+        &mut self,
+        idx: usize,
+        dest_pubkey: &[u8; 32],
+        handle: &[u8; 32],
+    ) -> Result<(), AppSW> {
+        if idx >= self.handles_verified.len() || idx >= self.blinders.len() {
+            return Err(AppSW::TxParsingFail);
+        }
+
+        verify_receiver_handle(dest_pubkey, &self.blinders[idx], handle)?;
+        self.handles_verified[idx] = true;
 
-        // Mark as verified
-        self.outputs_verified[idx] = true;
-        self.commitments_verified += 1;
+        Ok(())
+    }
 
+    /// Checks that the accumulated sum of verified output commitments, plus
+    /// `fee·G` and (if present) `burn·G`, matches the host-supplied net-spend
+    /// commitment. Because the same value is what the device signs over,
+    /// a host cannot present individually well-formed amounts that don't
+    /// actually sum to the real balance decrease.
+    pub fn verify_balance(
+        &mut self,
+        fee: u64,
+        burn: Option<u64>,
+        net_commitment: &[u8; 32],
+    ) -> Result<(), AppSW> {
+        let mut fee_scalar = [0u8; 32];
+        fee_scalar[24..32].copy_from_slice(&fee.to_be_bytes());
+        let fee_point = scalar_mult_ristretto(&fee_scalar, &XELIS_G_POINT)?;
+
+        let mut total = edwards_add(&self.balance_sum, &fee_point)?;
+
+        if let Some(burn_amount) = burn {
+            let mut burn_scalar = [0u8; 32];
+            burn_scalar[24..32].copy_from_slice(&burn_amount.to_be_bytes());
+            let burn_point = scalar_mult_ristretto(&burn_scalar, &XELIS_G_POINT)?;
+            total = edwards_add(&total, &burn_point)?;
+        }
+
+        let total_bytes = total.compress()?.to_le_bytes();
+        if total_bytes != *net_commitment {
+            return Err(AppSW::InvalidCommitment);
+        }
+
+        self.balance_verified = true;
+        Ok(())
+    }
+
+    /// Starts the aggregated Bulletproof range-proof verifier over every
+    /// output commitment confirmed so far. Idempotent: a second call while
+    /// already in progress is a no-op, since the proof stream only starts
+    /// once all transfers have been parsed.
+    pub fn ensure_range_proof_started(&mut self) {
+        if self.range_proof.is_none() {
+            self.range_proof = Some(BulletproofVerifier::new(self.commitments.clone()));
+        }
+    }
+
+    /// Streams one byte of the aggregated range proof into the verifier.
+    pub fn feed_range_proof_byte(&mut self, byte: u8) -> Result<(), AppSW> {
+        let done = self
+            .range_proof
+            .as_mut()
+            .ok_or(AppSW::TxParsingFail)?
+            .feed(byte)?;
+        if done {
+            self.range_proof_verified = true;
+        }
         Ok(())
     }
 
     pub fn all_verified(&self) -> bool {
         self.outputs_verified.iter().all(|&v| v)
+            && self.handles_verified.iter().all(|&v| v)
+            && self.range_proof_verified
+            && self.balance_verified
     }
 
     pub fn verified_count(&self) -> usize {
@@ -6,9 +6,16 @@ use alloc::string::String;
 use ledger_device_sdk::ecc::CxError;
 use ledger_device_sdk::random;
 
-/// Check if a scalar is zero
+/// Check if a scalar is zero, in constant time: folds every byte into an
+/// OR-accumulator instead of short-circuiting on the first non-zero byte, so
+/// callers checking private material or signature components (`k`, `x`)
+/// don't leak which byte of the secret first differs from zero.
 pub fn is_zero(scalar: &[u8; 32]) -> bool {
-    scalar.iter().all(|&b| b == 0)
+    let mut acc = 0u8;
+    for &b in scalar.iter() {
+        acc |= b;
+    }
+    acc == 0
 }
 
 /// Reduce a scalar modulo the group order L
@@ -124,55 +131,144 @@ pub fn scalar_from_bytes_wide(bytes: &[u8; 64]) -> Result<[u8; 32], AppSW> {
     Ok(out)
 }
 
-/// Create a deterministic scalar from seed material (for nonce generation)
-/// Uses HMAC-like construction for deterministic randomness
+/// HMAC output/block size: SHA3-512 digest is 64 bytes; the block (rate)
+/// size used for key padding is 72 bytes, per NIST SP 800-224's guidance for
+/// HMAC over SHA-3 (the Keccak rate for SHA3-512's 1024-bit capacity).
+const HMAC_SHA3_512_HLEN: usize = 64;
+const HMAC_SHA3_512_BLOCK_LEN: usize = 72;
+
+/// HMAC-SHA3-512, streamed through [`crate::crypto::sha::Sha3_512Stream`]
+/// rather than concatenated into a heap buffer first.
+fn hmac_sha3_512(key: &[u8], message: &[u8]) -> Result<[u8; HMAC_SHA3_512_HLEN], AppSW> {
+    let mut key_block = [0u8; HMAC_SHA3_512_BLOCK_LEN];
+    if key.len() > HMAC_SHA3_512_BLOCK_LEN {
+        let hashed = crate::crypto::sha::sha3_512(key)?;
+        key_block[..HMAC_SHA3_512_HLEN].copy_from_slice(&hashed);
+    } else {
+        key_block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut ipad = [0u8; HMAC_SHA3_512_BLOCK_LEN];
+    let mut opad = [0u8; HMAC_SHA3_512_BLOCK_LEN];
+    for i in 0..HMAC_SHA3_512_BLOCK_LEN {
+        ipad[i] = key_block[i] ^ 0x36;
+        opad[i] = key_block[i] ^ 0x5c;
+    }
+
+    let mut inner = crate::crypto::sha::Sha3_512Stream::new();
+    inner.update(&ipad)?;
+    inner.update(message)?;
+    let inner_hash = inner.finalize()?;
+
+    let mut outer = crate::crypto::sha::Sha3_512Stream::new();
+    outer.update(&opad)?;
+    outer.update(&inner_hash)?;
+    outer.finalize()
+}
+
+/// RFC6979 `bits2octets`: truncate `h1` to the leftmost 32 bytes (our group
+/// order `L` is byte-aligned at 256 bits, so `bits2int`'s bit-level
+/// truncation collapses to a byte-level one here), then reduce modulo `L`.
+fn bits2octets(h1: &[u8]) -> Result<[u8; 32], AppSW> {
+    let mut truncated = [0u8; 32];
+    if h1.len() >= 32 {
+        truncated.copy_from_slice(&h1[..32]);
+    } else {
+        truncated[32 - h1.len()..].copy_from_slice(h1);
+    }
+    scalar_reduce(&mut truncated).map_err(|_| AppSW::CryptoError)?;
+    Ok(truncated)
+}
+
+/// RFC6979 deterministic nonce generation (the same technique used for
+/// deterministic ECDSA/EdDSA), instantiated with HMAC-SHA3-512 since that's
+/// the hash already available on-device. `x` is the private scalar
+/// (big-endian, already reduced mod `L`); `h1` is the message hash.
+fn rfc6979_nonce(x: &[u8; 32], h1: &[u8]) -> Result<[u8; 32], AppSW> {
+    let bits2octets_h1 = bits2octets(h1)?;
+
+    let mut v = [0x01u8; HMAC_SHA3_512_HLEN];
+    let mut k = [0x00u8; HMAC_SHA3_512_HLEN];
+
+    let mut seed = [0u8; HMAC_SHA3_512_HLEN + 1 + 32 + 32];
+    seed[..HMAC_SHA3_512_HLEN].copy_from_slice(&v);
+    seed[HMAC_SHA3_512_HLEN] = 0x00;
+    seed[HMAC_SHA3_512_HLEN + 1..HMAC_SHA3_512_HLEN + 1 + 32].copy_from_slice(x);
+    seed[HMAC_SHA3_512_HLEN + 1 + 32..].copy_from_slice(&bits2octets_h1);
+    k = hmac_sha3_512(&k, &seed)?;
+    v = hmac_sha3_512(&k, &v)?;
+
+    seed[..HMAC_SHA3_512_HLEN].copy_from_slice(&v);
+    seed[HMAC_SHA3_512_HLEN] = 0x01;
+    k = hmac_sha3_512(&k, &seed)?;
+    v = hmac_sha3_512(&k, &v)?;
+
+    loop {
+        // hlen (64 bytes) >= qlen (32 bytes), so a single HMAC_K(V) already
+        // has enough bits and the `T` accumulation loop collapses to one step.
+        v = hmac_sha3_512(&k, &v)?;
+        let mut t = [0u8; 32];
+        t.copy_from_slice(&v[..32]);
+
+        if scalar_is_valid(&t) {
+            return Ok(t);
+        }
+
+        let mut retry_seed = [0u8; HMAC_SHA3_512_HLEN + 1];
+        retry_seed[..HMAC_SHA3_512_HLEN].copy_from_slice(&v);
+        retry_seed[HMAC_SHA3_512_HLEN] = 0x00;
+        k = hmac_sha3_512(&k, &retry_seed)?;
+        v = hmac_sha3_512(&k, &v)?;
+    }
+}
+
+/// Create a deterministic scalar from seed material (for nonce generation).
+/// Thin wrapper over [`rfc6979_nonce`] so existing callers of this signature
+/// keep working unchanged.
 pub fn scalar_deterministic(
     result: &mut [u8; 32],
     key: &[u8; 32],
     message: &[u8],
 ) -> Result<(), AppSW> {
-    // Simple deterministic approach: Hash(key || message)
-    // In production, you'd want proper HMAC or RFC6979
-
-    let mut combined = alloc::vec::Vec::new();
-    combined.extend_from_slice(key);
-    combined.extend_from_slice(message);
-
-    // Hash with SHA3-512 for wide reduction
-    let hash = crate::crypto::sha::sha3_512(&combined)?;
-
-    // Reduce to scalar
-    *result = scalar_from_bytes_wide(&hash)?;
-
+    *result = rfc6979_nonce(key, message)?;
     Ok(())
 }
 
-/// Convert a 32-byte array to scalar, ensuring it's reduced modulo L
-/// Input and output are both in big-endian format
+/// Convert a 32-byte array to a scalar, requiring it to already be a
+/// canonical encoding (non-zero and strictly less than `L`) rather than
+/// silently reducing it - a value `>= L` here is a malformed or malicious
+/// input, not something to quietly wrap.
 pub fn scalar_from_bytes(bytes: &[u8; 32]) -> Result<[u8; 32], AppSW> {
-    let mut result = *bytes;
-    scalar_reduce(&mut result).map_err(|_| AppSW::CryptoError)?;
-    Ok(result)
+    if !scalar_is_valid(bytes) {
+        return Err(AppSW::CryptoError);
+    }
+    Ok(*bytes)
 }
 
-/// Check if a scalar is valid (non-zero and less than L)
+/// Constant-time canonical-scalar check: non-zero and strictly less than the
+/// group order `L`. Folds a `lt`/`gt` accumulator pair across every byte,
+/// most-significant first, with no early `return`, so execution time doesn't
+/// depend on where a non-canonical scalar first diverges from `L` - the
+/// previous byte-by-byte early-return comparison leaked exactly that.
 pub fn scalar_is_valid(scalar: &[u8; 32]) -> bool {
-    // Check if non-zero
-    if is_zero(scalar) {
-        return false;
-    }
+    is_nonzero_ct(scalar) && is_less_than_l_ct(scalar)
+}
 
-    // Check if less than L (this is a simplified check)
-    // In big-endian, we can compare byte by byte from left to right
+/// Constant-time "at least one byte is non-zero" check.
+fn is_nonzero_ct(scalar: &[u8; 32]) -> bool {
+    !is_zero(scalar)
+}
+
+/// Constant-time big-endian `scalar < L`.
+fn is_less_than_l_ct(scalar: &[u8; 32]) -> bool {
+    let mut lt: u8 = 0; // latched to 1 the first time scalar[i] < L[i]
+    let mut gt: u8 = 0; // latched to 1 the first time scalar[i] > L[i]
     for i in 0..32 {
-        if scalar[i] < L[i] {
-            return true;
-        } else if scalar[i] > L[i] {
-            return false;
-        }
-        // If equal, continue to next byte
+        let undecided = !(lt | gt) & 0x01;
+        let byte_lt = (scalar[i] < L[i]) as u8;
+        let byte_gt = (scalar[i] > L[i]) as u8;
+        lt |= byte_lt & undecided;
+        gt |= byte_gt & undecided;
     }
-
-    // If we get here, scalar == L, which is invalid
-    false
+    lt != 0
 }
@@ -2,11 +2,45 @@ use crate::AppSW;
 use ledger_device_sdk::hash::sha3::Sha3_512;
 use ledger_device_sdk::hash::HashInit;
 
-/// SHA3-512 over `data`, returns 64-byte digest.
+/// Incremental SHA3-512: feed bytes in as they arrive (e.g. one APDU chunk
+/// at a time) instead of buffering the whole message into a `Vec` first.
+/// Wraps the SDK hasher so call sites get `AppSW`-mapped errors directly
+/// instead of repeating `.map_err(|_| AppSW::TxHashFail)` at every call.
+pub struct Sha3_512Stream {
+    inner: Sha3_512,
+}
+
+impl Sha3_512Stream {
+    pub fn new() -> Self {
+        Self {
+            inner: Sha3_512::new(),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), AppSW> {
+        self.inner.update(data).map_err(|_| AppSW::TxHashFail)
+    }
+
+    pub fn finalize(&mut self) -> Result<[u8; 64], AppSW> {
+        let mut digest = [0u8; 64];
+        self.inner
+            .finalize(&mut digest)
+            .map_err(|_| AppSW::TxHashFail)?;
+        Ok(digest)
+    }
+}
+
+impl Default for Sha3_512Stream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One-shot SHA3-512 over `data`, returns 64-byte digest. Thin wrapper
+/// around [`Sha3_512Stream`] for callers (and tests) that already have the
+/// whole message in hand.
 pub fn sha3_512(data: &[u8]) -> Result<[u8; 64], AppSW> {
-    let mut digest = [0u8; 64];
-    let mut hasher = Sha3_512::new();
-    hasher.update(data).map_err(|_| AppSW::TxHashFail)?;
-    hasher.finalize(&mut digest).map_err(|_| AppSW::TxHashFail)?;
-    Ok(digest)
+    let mut stream = Sha3_512Stream::new();
+    stream.update(data)?;
+    stream.finalize()
 }
\ No newline at end of file
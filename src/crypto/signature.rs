@@ -1,11 +1,16 @@
-use crate::crypto::{ristretto::*, scalar, scalar::*, sha::sha3_512, *};
+use crate::crypto::{ristretto::*, scalar, scalar::*, sha::Sha3_512Stream, *};
 use crate::cx::*;
 use crate::AppSW;
-use alloc::vec;
+use ledger_device_sdk::random;
 
 /// XELIS signature: s || e (both 32-byte big-endian)
 pub const SIGNATURE_SIZE: usize = 64;
 
+/// Domain-separation prefix for `handler_sign_message`. Hashed in front of
+/// every arbitrary user message so a signed message digest can never collide
+/// with (and be replayed as) a serialized transaction hash.
+pub const XELIS_MESSAGE_TAG: &[u8] = b"\x01XELIS Signed Message:\n";
+
 pub struct XelisSignature {
     pub s: [u8; 32], // BE
     pub e: [u8; 32], // BE
@@ -34,22 +39,23 @@ impl XelisSignature {
 
 /// Compute the XELIS challenge scalar e using **wide** reduction:
 ///   e = reduce_wide( SHA3-512( A_compressed || message || R_compressed ) )
+///
+/// Feeds `A_le`, then `message`, then `R_le` straight into a running hash
+/// instead of concatenating them into a heap buffer first, so the size of
+/// `message` never shows up as an allocation here.
 pub fn xelis_challenge_from_hash(
     a_comp: &CompressedRistretto,
     message: &[u8],
     r_comp: &CompressedRistretto,
 ) -> Result<[u8; 32], AppSW> {
-    // Concat: A || msg || R  (all as bytes actually hashed by XELIS)
     let a_le = a_comp.to_le_bytes();
     let r_le = r_comp.to_le_bytes();
 
-    let mut buf = vec![0u8; 32 + message.len() + 32];
-    buf[..32].copy_from_slice(&a_le);
-    buf[32..32 + message.len()].copy_from_slice(message);
-    buf[32 + message.len()..].copy_from_slice(&r_le);
-
-    // SHA3-512
-    let wide = sha3_512(&buf)?; // 64 bytes
+    let mut hasher = Sha3_512Stream::new();
+    hasher.update(&a_le)?;
+    hasher.update(message)?;
+    hasher.update(&r_le)?;
+    let wide = hasher.finalize()?; // 64 bytes
 
     // Wide reduction mod L -> 32B BE
     // IMPORTANT: do a *wide* mod, not "take 32 then reduce".
@@ -90,8 +96,14 @@ pub fn schnorr_sign(
         return Err(AppSW::TxSignFail);
     }
 
-    // 1) Deterministic nonce k (keep what you had, but ensure nonzero + reduced)
+    // 1) Nonce k: hedged by default (deterministic hash folded with fresh
+    // TRNG bytes) so a glitched resign of the same message can't leak the
+    // key the way a purely deterministic nonce would; a purely
+    // deterministic build is available for reproducing fixed test vectors.
+    #[cfg(feature = "deterministic-nonce")]
     let mut k_be = det_nonce_be(private_key_be, message_hash)?;
+    #[cfg(not(feature = "deterministic-nonce"))]
+    let mut k_be = hedged_nonce_be(private_key_be, message_hash)?;
     if scalar::is_zero(&k_be) {
         return Err(AppSW::TxSignFail);
     }
@@ -113,15 +125,40 @@ pub fn schnorr_sign(
     Ok(XelisSignature { s: s_be, e: e_be })
 }
 
-/// Deterministic nonce k (simple, reproducible): H(private || msg) → wide reduce → BE
-pub fn det_nonce_be(sk_be: &[u8; 32], msg: &[u8]) -> Result<[u8; 32], AppSW> {
-    let mut inbuf = vec![0u8; 32 + msg.len()];
-    inbuf[..32].copy_from_slice(sk_be);
-    inbuf[32..].copy_from_slice(msg);
-    let wide = sha3_512(&inbuf)?; // 64 bytes
+/// Hedged nonce k: H(private || Z || msg) → wide reduce → BE, where `Z` is
+/// 32 fresh bytes from the device TRNG. Folding in `Z` means a fault that
+/// perturbs the signing computation can no longer force the same nonce to
+/// reappear across two signatures of the same message, while still mixing
+/// in the private key and message so a weak RNG alone can't break it either.
+/// Verifiers are unaffected - `e = H(A||msg||R)` only depends on the
+/// resulting `R = k·H`, not on how `k` was derived.
+pub fn hedged_nonce_be(sk_be: &[u8; 32], msg: &[u8]) -> Result<[u8; 32], AppSW> {
+    loop {
+        let mut z = [0u8; 32];
+        random::rand_bytes(&mut z);
+
+        let mut hasher = Sha3_512Stream::new();
+        hasher.update(sk_be)?;
+        hasher.update(&z)?;
+        hasher.update(msg)?;
+        let wide = hasher.finalize()?; // 64 bytes
+
+        let mut k_be = [0u8; 32];
+        reduce_mod_l_wide_le_to_be(&wide, &mut k_be)?;
+        if !scalar::is_zero(&k_be) {
+            return Ok(k_be);
+        }
+    }
+}
 
+/// Deterministic nonce k: RFC6979 over HMAC-SHA3-512, keyed by the private
+/// scalar and the message hash. Only compiled in behind the
+/// `deterministic-nonce` feature, which trades away the hedging above to
+/// keep fixed test vectors reproducible.
+#[cfg(feature = "deterministic-nonce")]
+pub fn det_nonce_be(sk_be: &[u8; 32], msg: &[u8]) -> Result<[u8; 32], AppSW> {
     let mut k_be = [0u8; 32];
-    reduce_mod_l_wide_le_to_be(&wide, &mut k_be)?;
+    scalar::scalar_deterministic(&mut k_be, sk_be, msg)?;
     if scalar::is_zero(&k_be) {
         // extremely unlikely, but reject anyway
         return Err(AppSW::TxSignFail);
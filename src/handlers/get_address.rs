@@ -0,0 +1,108 @@
+use crate::app_ui::address::{ui_display_integrated_address, ui_display_pk};
+use crate::crypto::address::{Address, MAX_INTEGRATED_DATA_LEN};
+use crate::crypto::public_key::XelisPublicKey;
+use crate::crypto::ristretto::*;
+use crate::crypto::secure::with_derived_key;
+use crate::utils::Bip32Path;
+use crate::AppSW;
+use ledger_device_sdk::io::Comm;
+
+/// Derives the receive address for a BIP32 path and returns it bech32-encoded,
+/// optionally asking the user to confirm it on-screen first (`confirm == true`).
+///
+/// The payload may carry two optional trailing fields after the path:
+/// `[integrated_len(1)][integrated_data(..)]` to have the device embed a
+/// payment-ID/structured-data payload and derive an `AddressType::Integrated`
+/// address instead of a normal one, and `[verify_len(1)][verify_addr(..)]`, a
+/// host-supplied ASCII address. When the latter is present, the device
+/// decodes it with [`Address::from_bytes`] and compares the result against
+/// the address it just derived, so a host that mangles the string before
+/// showing it to the user can't slip the mismatch past this device's
+/// confirmation screen.
+pub fn handler_get_address(comm: &mut Comm, confirm: bool) -> Result<(), AppSW> {
+    let data = comm.get_data().map_err(|_| AppSW::WrongApduLength)?;
+    if data.is_empty() {
+        return Err(AppSW::WrongApduLength);
+    }
+
+    let path_bytes_end = 1 + data[0] as usize * 4;
+    if data.len() < path_bytes_end {
+        return Err(AppSW::WrongApduLength);
+    }
+    let path: Bip32Path = data[..path_bytes_end].try_into()?;
+
+    let mut offset = path_bytes_end;
+    let integrated_data: Option<&[u8]> = if offset < data.len() {
+        let len = data[offset] as usize;
+        offset += 1;
+        if len > MAX_INTEGRATED_DATA_LEN || offset + len > data.len() {
+            return Err(AppSW::WrongApduLength);
+        }
+        let slice = &data[offset..offset + len];
+        offset += len;
+        if len == 0 {
+            None
+        } else {
+            Some(slice)
+        }
+    } else {
+        None
+    };
+
+    let verify_addr: Option<&[u8]> = if offset < data.len() {
+        let len = data[offset] as usize;
+        let start = offset + 1;
+        if start + len != data.len() {
+            return Err(AppSW::WrongApduLength);
+        }
+        if len == 0 {
+            None
+        } else {
+            Some(&data[start..start + len])
+        }
+    } else {
+        None
+    };
+
+    let pk_le = with_derived_key(path.as_ref(), |scalar| {
+        let pk_comp =
+            xelis_public_from_private(scalar.as_ref()).map_err(|_| AppSW::KeyDeriveFail)?;
+        Ok(pk_comp.to_le_bytes())
+    })?;
+
+    let is_mainnet = true;
+    let xpk = XelisPublicKey::new(CompressedRistretto::from_le_bytes(pk_le));
+    let addr = match integrated_data {
+        Some(payload) => Address::new_integrated(is_mainnet, xpk, payload),
+        None => Address::new(is_mainnet, xpk),
+    };
+    let (addr_bytes, len) = addr.to_bytes().map_err(|_| AppSW::AddrDisplayFail)?;
+
+    if let Some(expected) = verify_addr {
+        let decoded = Address::from_bytes(expected)?;
+        let (decoded_bytes, decoded_len) =
+            decoded.to_bytes().map_err(|_| AppSW::AddrDisplayFail)?;
+        if decoded_len != len || decoded_bytes[..decoded_len] != addr_bytes[..len] {
+            return Err(AppSW::AddressMismatch);
+        }
+    }
+
+    if confirm {
+        let approved = match integrated_data {
+            Some(payload) => {
+                let addr_str = core::str::from_utf8(&addr_bytes[..len])
+                    .map_err(|_| AppSW::AddrDisplayFail)?;
+                ui_display_integrated_address(addr_str, payload)?
+            }
+            None => ui_display_pk(&pk_le)?,
+        };
+        if !approved {
+            return Err(AppSW::Deny);
+        }
+    }
+
+    comm.append(&[len as u8]);
+    comm.append(&addr_bytes[..len]);
+
+    Ok(())
+}
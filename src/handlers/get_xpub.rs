@@ -0,0 +1,39 @@
+use crate::crypto::address::Address;
+use crate::crypto::public_key::XelisPublicKey;
+use crate::crypto::ristretto::*;
+use crate::crypto::secure::with_derived_key_chain;
+use crate::utils::Bip32Path;
+use crate::AppSW;
+use ledger_device_sdk::io::Comm;
+
+/// Exports an extended public key for an arbitrary host-supplied BIP32 path:
+/// the compressed Ristretto public key, its chain code, and the path depth,
+/// plus the first `xel:`/`xet:` address it derives - enough for a host to
+/// build a watch-only wallet and derive further addresses offline without
+/// ever touching the private key again.
+pub fn handler_get_xpub(comm: &mut Comm) -> Result<(), AppSW> {
+    let data = comm.get_data().map_err(|_| AppSW::WrongApduLength)?;
+    let path: Bip32Path = data.try_into()?;
+    path.validate()?;
+
+    let (pk_le, chain_code) = with_derived_key_chain(path.as_ref(), |scalar, chain_code| {
+        let pk_comp =
+            xelis_public_from_private(scalar.as_ref()).map_err(|_| AppSW::KeyDeriveFail)?;
+        Ok((pk_comp.to_le_bytes(), *chain_code.as_ref()))
+    })?;
+
+    let is_mainnet = true;
+    let xpk = XelisPublicKey::new(CompressedRistretto::from_le_bytes(pk_le));
+    let addr = Address::new(is_mainnet, xpk);
+    let (addr_bytes, len) = addr.to_bytes().map_err(|_| AppSW::AddrDisplayFail)?;
+
+    comm.append(&[32u8]);
+    comm.append(&pk_le);
+    comm.append(&[32u8]);
+    comm.append(&chain_code);
+    comm.append(&[path.as_ref().len() as u8]);
+    comm.append(&[len as u8]);
+    comm.append(&addr_bytes[..len]);
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use crate::app_ui::sign::ui_display_multisig_partial_sign;
+use crate::crypto::ristretto::xelis_public_from_private;
+use crate::crypto::secure::with_derived_key;
+use crate::crypto::signature::{schnorr_sign, SIGNATURE_SIZE};
+use crate::handlers::sign_tx::{
+    begin_tx_stream, finalize_tx_hash, parse_and_verify_stream, validate_and_count_chunk,
+    TxContext,
+};
+use crate::utils::Bip32Path;
+use crate::AppSW;
+use ledger_device_sdk::io::Comm;
+
+const PARTIAL_SIG_RECORD_LEN: usize = 1 + SIGNATURE_SIZE;
+
+/// Cosigner round for a Xelis multisig transaction, modeled after the BIP174
+/// Updater/Signer split: the host has collected whatever partial signatures
+/// exist so far from other participants and hands them back here alongside
+/// the full transaction body, which this device streams and verifies
+/// exactly as `handler_sign_tx` would - the hash it ultimately signs is
+/// derived on-device from the real, approved transaction bytes, never taken
+/// as an opaque host-supplied blob.
+///
+/// Chunk 0 payload: `[path_len(1)][path(4*path_len)][signer_index(1)][threshold(1)]
+/// [partial_count(1)][{signer_index(1),signature(64)} * partial_count]`.
+/// Every following chunk carries raw transaction bytes, chunked the same way
+/// as `SignTx`.
+pub fn handler_multisig_sign(
+    comm: &mut Comm,
+    chunk: u8,
+    more: bool,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
+    let data = comm.get_data().map_err(|_| AppSW::WrongApduLength)?;
+
+    if chunk == 0 {
+        if data.is_empty() {
+            return Err(AppSW::WrongApduLength);
+        }
+
+        let path_bytes_end = 1 + data[0] as usize * 4;
+        if data.len() < path_bytes_end {
+            return Err(AppSW::WrongApduLength);
+        }
+        let path: Bip32Path = data[..path_bytes_end].try_into()?;
+        path.validate()?;
+
+        let mut offset = path_bytes_end;
+        if data.len() < offset + 3 {
+            return Err(AppSW::WrongApduLength);
+        }
+        let signer_index = data[offset];
+        let threshold = data[offset + 1];
+        let partial_count = data[offset + 2] as usize;
+        offset += 3;
+
+        if data.len() != offset + partial_count * PARTIAL_SIG_RECORD_LEN {
+            return Err(AppSW::WrongApduLength);
+        }
+
+        // This device's slot must still be open, and the threshold must not
+        // already be satisfied - otherwise a host could keep re-requesting
+        // this device's signature after the multisig is already spendable.
+        let already_signed = data[offset..]
+            .chunks(PARTIAL_SIG_RECORD_LEN)
+            .any(|record| record[0] == signer_index);
+        if already_signed {
+            return Err(AppSW::MultiSigAlreadySigned);
+        }
+        if partial_count as u8 >= threshold {
+            return Err(AppSW::MultiSigThresholdMet);
+        }
+
+        // Streams and verifies the real transaction body the same way a
+        // solo signer's chunk 0 would - this is what the rest of the round
+        // hashes and signs, not the host-supplied blob the old protocol used.
+        begin_tx_stream(ctx, path)?;
+        ctx.multisig_signer_index = signer_index;
+        ctx.multisig_threshold = threshold;
+        ctx.multisig_partial_count = partial_count as u8;
+
+        return Ok(());
+    }
+
+    if data.is_empty() {
+        return Err(AppSW::TxParsingFail);
+    }
+
+    validate_and_count_chunk(ctx, chunk, data.len())?;
+    parse_and_verify_stream(ctx, data)?;
+
+    if !more {
+        let tx_hash = finalize_tx_hash(ctx)?;
+
+        if !ui_display_multisig_partial_sign(
+            ctx.multisig_signer_index,
+            ctx.multisig_threshold,
+            ctx.multisig_partial_count,
+        )? {
+            return Err(AppSW::Deny);
+        }
+
+        let signer_index = ctx.multisig_signer_index;
+        let path = ctx.signing_path();
+        with_derived_key(path, |private_key| {
+            let pubkey = xelis_public_from_private(private_key.as_ref())?;
+            let signature = schnorr_sign(private_key.as_ref(), &pubkey, &tx_hash)?;
+
+            comm.append(&[signer_index]);
+            comm.append(&[SIGNATURE_SIZE as u8]);
+            comm.append(&signature.to_le_bytes());
+
+            Ok(())
+        })?;
+
+        ctx.sign_succeeded = true;
+        ctx.sign_completed = true;
+    }
+
+    Ok(())
+}
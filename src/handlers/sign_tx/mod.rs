@@ -1,9 +1,14 @@
 use crate::{
-    app_ui::sign::ui_display_memo_tx,
+    app_ui::sign::{
+        ui_display_contract_call, ui_display_deploy_contract, ui_display_extra_data,
+        ui_display_memo_multisig, ui_display_memo_tx, ui_display_message_hash, ui_display_multisig,
+    },
     crypto::{
-        commitment::{verify_pedersen_commitment, CommitmentVerifier},
+        chacha20poly1305::{derive_extra_data_key_and_nonce_from_handle, StreamingAeadDecryptor},
+        commitment::CommitmentVerifier,
         ristretto::*,
         secure::*,
+        sha::Sha3_512Stream,
         signature::*,
     },
     utils::Bip32Path,
@@ -11,9 +16,6 @@ use crate::{
     *,
     AppSW,
 };
-use alloc::vec::Vec;
-use ledger_device_sdk::hash::sha3::Sha3_512;
-use ledger_device_sdk::hash::HashInit;
 use ledger_device_sdk::io::Comm;
 
 mod tx_parser;
@@ -41,21 +43,28 @@ const MAX_CHUNKS: u16 = 4500;
 // #[cfg(not(any(target_os = "nanos", target_os = "nanox", target_os = "nanosplus", target_os = "stax", target_os = "flex")))]
 // const MAX_MEMO_SIZE: usize = 4 * 1024;
 
-const MAX_MEMO_SIZE: usize = 3 * 1024 + 512;  // 3.5KB
-
 pub struct TxContext {
-    // Hashing
-    tx_hasher: Sha3_512,
+    // Canonical/signed-region hashing: headers, transfers, recipient
+    // commitments, fee, nonce. This is what actually gets signed.
+    tx_hasher: Sha3_512Stream,
     tx_hash: Option<[u8; 64]>,
 
+    // Witness-region hashing: range proofs, sigma proofs and the signature
+    // itself never flow into `tx_hasher`, but still get their own digest so
+    // `finalize_transaction` can check it against the host-declared value.
+    witness_hasher: Sha3_512Stream,
+    witness_hash: Option<[u8; 64]>,
+
     // Path and metadata
     path: Bip32Path,
     total_size: usize,
     chunk_count: u16,
 
-    // Memo handling
+    // Memo handling. The memo TLV is parsed incrementally as chunks arrive
+    // rather than buffered whole first - `memo_parser` only ever holds the
+    // single TLV record currently straddling a chunk boundary.
     memo: Option<MemoPreview>,
-    memo_buffer: Vec<u8>,
+    memo_parser: MemoStreamParser,
     memo_chunk_count: usize,
     preview_approved: bool,
 
@@ -63,44 +72,91 @@ pub struct TxContext {
     pub sign_completed: bool,
     pub sign_succeeded: bool,
 
+    // Multisig cosign metadata, set on `handler_multisig_sign`'s chunk 0 and
+    // consulted once the streamed tx body's hash has been verified on the
+    // final chunk.
+    pub(crate) multisig_signer_index: u8,
+    pub(crate) multisig_threshold: u8,
+    pub(crate) multisig_partial_count: u8,
+
     // Delegated components
     parser: TxStreamParser,
     verifier: CommitmentVerifier,
+
+    // Message-signing state (handler_sign_message)
+    msg_hasher: Sha3_512Stream,
+    msg_hash: Option<[u8; 64]>,
+    msg_path: Bip32Path,
+    msg_total_size: usize,
+    msg_chunk_count: u16,
+    pub msg_sign_completed: bool,
+    pub msg_sign_succeeded: bool,
 }
 
 impl TxContext {
     pub fn new() -> Self {
         Self {
-            tx_hasher: Sha3_512::new(),
+            tx_hasher: Sha3_512Stream::new(),
             tx_hash: None,
+            witness_hasher: Sha3_512Stream::new(),
+            witness_hash: None,
             path: Default::default(),
             total_size: 0,
             chunk_count: 0,
             memo: None,
-            memo_buffer: Vec::new(),
+            memo_parser: MemoStreamParser::new(),
             memo_chunk_count: 0,
             preview_approved: false,
             sign_completed: false,
             sign_succeeded: false,
+            multisig_signer_index: 0,
+            multisig_threshold: 0,
+            multisig_partial_count: 0,
             parser: TxStreamParser::new(),
             verifier: CommitmentVerifier::new(),
+            msg_hasher: Sha3_512Stream::new(),
+            msg_hash: None,
+            msg_path: Default::default(),
+            msg_total_size: 0,
+            msg_chunk_count: 0,
+            msg_sign_completed: false,
+            msg_sign_succeeded: false,
         }
     }
 
     pub fn reset(&mut self) {
-        self.tx_hasher = Sha3_512::new();
+        self.tx_hasher = Sha3_512Stream::new();
         self.tx_hash = None;
+        self.witness_hasher = Sha3_512Stream::new();
+        self.witness_hash = None;
         self.path = Default::default();
         self.total_size = 0;
         self.chunk_count = 0;
         self.memo = None;
-        self.memo_buffer.clear();
+        self.memo_parser.reset();
         self.memo_chunk_count = 0;
         self.preview_approved = false;
         self.sign_completed = false;
         self.sign_succeeded = false;
+        self.multisig_signer_index = 0;
+        self.multisig_threshold = 0;
+        self.multisig_partial_count = 0;
         self.parser.reset();
         self.verifier.reset();
+        self.msg_hasher = Sha3_512Stream::new();
+        self.msg_hash = None;
+        self.msg_path = Default::default();
+        self.msg_total_size = 0;
+        self.msg_chunk_count = 0;
+        self.msg_sign_completed = false;
+        self.msg_sign_succeeded = false;
+    }
+
+    /// The BIP32 path `begin_tx_stream` stored for the transaction currently
+    /// streaming in, so `handler_multisig_sign` can sign with it once the
+    /// tx-body hash has been verified on the final chunk.
+    pub(crate) fn signing_path(&self) -> &[u32] {
+        self.path.as_ref()
     }
 }
 
@@ -113,7 +169,7 @@ pub fn handler_load_memo(
     let data = comm.get_data().map_err(|_| AppSW::WrongApduLength)?;
 
     if chunk == 0 {
-        ctx.memo_buffer.clear();
+        ctx.memo_parser.reset();
         ctx.memo_chunk_count = 0;
         ctx.memo = None;
         ctx.preview_approved = false;
@@ -132,22 +188,27 @@ pub fn handler_load_memo(
 
     ctx.memo_chunk_count += 1;
 
-    if ctx.memo_buffer.len() + data.len() > MAX_MEMO_SIZE {
-        return Err(AppSW::MemoTooLarge);
-    }
-
-    ctx.memo_buffer.extend_from_slice(data);
+    // Fed straight into the resumable parser - at most the one TLV record
+    // straddling this chunk boundary is held in memory, not the whole memo.
+    ctx.memo_parser.feed(data)?;
 
     if more {
         return Ok(());
     }
 
     // Parse and approve memo
-    let preview = parse_memo_tlv(&mut ctx.memo_buffer)?;
-    // let parsed = memo_to_parsed_tx(&preview);
+    let preview = ctx.memo_parser.finalize()?;
 
-    // Ok(())
-    if ui_display_memo_tx(&preview)? {
+    // MultiSig carries no outputs of its own, so it gets its own dedicated
+    // threshold/participant-set approval screen rather than the generic
+    // output-list preview.
+    let approved = if preview.tx_type == TX_MULTISIG {
+        ui_display_memo_multisig()?
+    } else {
+        ui_display_memo_tx(&preview)?
+    };
+
+    if approved {
         ctx.memo = Some(preview);
         ctx.preview_approved = true;
         Ok(())
@@ -212,40 +273,96 @@ pub fn handler_sign_tx(
     }
 
     if chunk == 0 {
-        if !ctx.preview_approved {
-            return Err(AppSW::MemoRequired);
-        }
+        begin_tx_stream(ctx, data.try_into()?)?;
+        return Ok(());
+    }
+
+    validate_and_count_chunk(ctx, chunk, data.len())?;
+
+    // Parse and verify - this drives which bytes flow into the canonical
+    // (signed) hash vs. the witness hash, since the boundary depends on the
+    // tx type and where proof data begins.
+    parse_and_verify_stream(ctx, data)?;
+
+    if !more {
+        finalize_transaction(comm, ctx)?;
+        ctx.sign_succeeded = true;
+        ctx.sign_completed = true;
+    }
+
+    Ok(())
+}
+
+/// Resets all per-transaction streaming state and primes `ctx.verifier` for
+/// a fresh tx body, given the BIP32 path that will ultimately sign it.
+/// Shared by `handler_sign_tx`'s chunk 0 and `handler_multisig_sign`'s chunk
+/// 0, since a multisig cosign round has to stream and verify the same real
+/// transaction bytes a solo signer would, not a host-supplied hash.
+pub(crate) fn begin_tx_stream(ctx: &mut TxContext, path: Bip32Path) -> Result<(), AppSW> {
+    if !ctx.preview_approved {
+        return Err(AppSW::MemoRequired);
+    }
 
-        ctx.sign_completed = false;
-        ctx.sign_succeeded = false;
-        ctx.tx_hasher = Sha3_512::new();
-        ctx.tx_hash = None;
-        ctx.total_size = 0;
-        ctx.chunk_count = 0;
-        ctx.path = data.try_into()?;
-        ctx.parser.reset();
-
-        // Initialize verification
-        unsafe {
-            if let Some(memo) = &ctx.memo {
-                if memo.tx_type == 0 || memo.tx_type == 1 {
+    ctx.sign_completed = false;
+    ctx.sign_succeeded = false;
+    ctx.tx_hasher = Sha3_512Stream::new();
+    ctx.tx_hash = None;
+    ctx.witness_hasher = Sha3_512Stream::new();
+    ctx.witness_hash = None;
+    ctx.total_size = 0;
+    ctx.chunk_count = 0;
+    ctx.path = path;
+    ctx.parser.reset();
+
+    // Initialize verification, sized to whatever this tx type's memo
+    // preview says it needs to verify against: confidential outputs for
+    // Transfer/Burn, or the plain deposit list for a contract call/deploy.
+    unsafe {
+        if let Some(memo) = &ctx.memo {
+            match memo.tx_type {
+                TX_TRANSFER | TX_BURN => {
                     ctx.verifier.init_verification(memo_ws_mut().outs.len());
                 }
+                TX_INVOKE_CONTRACT => {
+                    let count = memo_ws_mut()
+                        .invoke
+                        .as_ref()
+                        .map(|i| i.deposits.len())
+                        .unwrap_or(0);
+                    ctx.verifier.init_verification(count);
+                }
+                TX_DEPLOY_CONTRACT => {
+                    let count = memo_ws_mut()
+                        .deploy
+                        .as_ref()
+                        .map(|d| d.deposits.len())
+                        .unwrap_or(0);
+                    ctx.verifier.init_verification(count);
+                }
+                _ => {}
             }
         }
-
-        return Ok(());
     }
 
-    // Validate chunk sequence
+    Ok(())
+}
+
+/// Validates this chunk's sequence number against `ctx.chunk_count` and the
+/// running total size, the same bookkeeping `handler_sign_tx` and
+/// `handler_multisig_sign` both need for every non-first chunk of a
+/// streamed transaction body.
+pub(crate) fn validate_and_count_chunk(
+    ctx: &mut TxContext,
+    chunk: u8,
+    data_len: usize,
+) -> Result<(), AppSW> {
     let expected_p1 = ((ctx.chunk_count % 255) as u8) + 1;
     if chunk != expected_p1 {
         return Err(AppSW::TxParsingFail);
     }
     ctx.chunk_count += 1;
 
-    // Size checks
-    ctx.total_size += data.len();
+    ctx.total_size += data_len;
     if ctx.total_size > MAX_TRANSACTION_LEN {
         return Err(AppSW::TxWrongLength);
     }
@@ -253,28 +370,85 @@ pub fn handler_sign_tx(
         return Err(AppSW::TxParsingFail);
     }
 
-    // Stream hash
-    ctx.tx_hasher.update(data).map_err(|_| AppSW::TxHashFail)?;
+    Ok(())
+}
 
-    // Parse and verify
-    parse_and_verify_stream(ctx, data)?;
+const MAX_MESSAGE_LEN: usize = 4096;
 
-    if !more {
-        finalize_transaction(comm, ctx)?;
-        ctx.sign_succeeded = true;
-        ctx.sign_completed = true;
+/// Signs an arbitrary user message rather than a transaction, for
+/// proof-of-ownership / login-style flows. Chunked the same way as
+/// `handler_sign_tx`: chunk 0 carries the BIP32 path, later chunks carry the
+/// message bytes which are streamed into a hasher rather than buffered whole.
+pub fn handler_sign_message(
+    comm: &mut Comm,
+    chunk: u8,
+    more: bool,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
+    let data = comm.get_data().map_err(|_| AppSW::WrongApduLength)?;
+
+    if data.is_empty() {
+        return Err(AppSW::TxParsingFail);
+    }
+
+    if chunk == 0 {
+        ctx.msg_sign_completed = false;
+        ctx.msg_sign_succeeded = false;
+        ctx.msg_hasher = Sha3_512Stream::new();
+        ctx.msg_hasher
+            .update(XELIS_MESSAGE_TAG)?;
+        ctx.msg_hash = None;
+        ctx.msg_total_size = 0;
+        ctx.msg_chunk_count = 0;
+        ctx.msg_path = data.try_into()?;
+
+        return Ok(());
+    }
+
+    // Validate chunk sequence
+    let expected_chunk = ((ctx.msg_chunk_count % 255) as u8) + 1;
+    if chunk != expected_chunk {
+        return Err(AppSW::TxParsingFail);
+    }
+    ctx.msg_chunk_count += 1;
+
+    ctx.msg_total_size += data.len();
+    if ctx.msg_total_size > MAX_MESSAGE_LEN {
+        return Err(AppSW::TxWrongLength);
+    }
+
+    // Stream the message into the domain-separated hash; never buffered whole.
+    ctx.msg_hasher.update(data)?;
+
+    if more {
+        return Ok(());
+    }
+
+    let hash = ctx.msg_hasher.finalize()?;
+    ctx.msg_hash = Some(hash);
+
+    if !ui_display_message_hash(&hash)? {
+        return Err(AppSW::Deny);
     }
 
+    sign_hash_and_append(comm, ctx.msg_path.as_ref(), &hash)?;
+
+    ctx.msg_sign_succeeded = true;
+    ctx.msg_sign_completed = true;
+
     Ok(())
 }
 
-fn parse_and_verify_stream(ctx: &mut TxContext, data: &[u8]) -> Result<(), AppSW> {
+pub(crate) fn parse_and_verify_stream(ctx: &mut TxContext, data: &[u8]) -> Result<(), AppSW> {
     let memo = ctx.memo.as_ref().ok_or(AppSW::MemoInvalid)?;
     let mut offset = 0;
 
-    // Parse header if needed
+    // Parse header if needed - always canonical (signed).
     if ctx.parser.bytes_seen < 35 {
-        offset += ctx.parser.parse_header(&data[offset..], memo)?;
+        let consumed = ctx.parser.parse_header(&data[offset..], memo)?;
+        ctx.tx_hasher
+            .update(&data[offset..offset + consumed])?;
+        offset += consumed;
     }
 
     match memo.tx_type {
@@ -283,7 +457,7 @@ fn parse_and_verify_stream(ctx: &mut TxContext, data: &[u8]) -> Result<(), AppSW
             if ctx.parser.in_transfers {
                 while ctx.parser.transfers_parsed < ctx.parser.transfer_count && offset < data.len()
                 {
-                    let (commitment, consumed) = ctx
+                    let (commitment, consumed, witness_consumed) = ctx
                         .parser
                         .extract_commitment_from_transfer(&data[offset..])?;
 
@@ -291,19 +465,129 @@ fn parse_and_verify_stream(ctx: &mut TxContext, data: &[u8]) -> Result<(), AppSW
                         if let Some(c) = commitment {
                             let idx = (ctx.parser.transfers_parsed - 1) as usize;
                             let amount = memo_ws_mut().outs[idx].amount;
-                            ctx.verifier.verify_output(idx, &c, amount)?;
+                            ctx.verifier.record_output(idx, &c, amount)?;
+                        }
+                    }
+
+                    // Once the witness tail for this transfer has fully
+                    // streamed in, recompute its receiver handle and check it
+                    // against the recipient from the approved memo - a
+                    // well-formed commitment paired with the wrong handle
+                    // would otherwise silently burn funds the recipient can
+                    // never decrypt.
+                    if ctx.parser.receiver_handle_ready {
+                        ctx.parser.receiver_handle_ready = false;
+                        unsafe {
+                            let idx = (ctx.parser.transfers_parsed - 1) as usize;
+                            let dest = memo_ws_mut().outs[idx].dest;
+                            ctx.verifier
+                                .verify_handle(idx, &dest, &ctx.parser.receiver_handle)?;
+                        }
+
+                        // The sender handle is also this transfer's ECDH
+                        // point for extra_data: it only finishes streaming
+                        // in with the rest of the witness tail, well after
+                        // the ciphertext it keys, so extra_data is buffered
+                        // whole during its own state and decrypted here
+                        // instead of as it streams.
+                        if ctx.parser.extra_ciphertext_ready {
+                            ctx.parser.extra_ciphertext_ready = false;
+                            if let Some((asset, dest)) = ctx.parser.pending_asset_dest {
+                                let path = ctx.path.as_ref();
+                                let handle = ctx.parser.sender_handle;
+                                let (key, nonce) = with_derived_key(path, |private_key| {
+                                    derive_extra_data_key_and_nonce_from_handle(
+                                        private_key.as_ref(),
+                                        &handle,
+                                    )
+                                })?;
+
+                                let mut aad = [0u8; 64];
+                                aad[..32].copy_from_slice(&asset);
+                                aad[32..].copy_from_slice(&dest);
+
+                                let mut decryptor =
+                                    StreamingAeadDecryptor::new(&key, &nonce, &aad)?;
+                                ctx.parser.extra_plaintext.clear();
+                                for &byte in &ctx.parser.extra_ciphertext {
+                                    let plain = decryptor.absorb_ciphertext_byte(byte)?;
+                                    if ctx.parser.extra_plaintext.len() < MAX_EXTRA_DATA_DISPLAY {
+                                        ctx.parser.extra_plaintext.push(plain);
+                                    }
+                                }
+                                decryptor.finalize(&ctx.parser.extra_tag)?;
+                                ctx.parser.extra_ciphertext.clear();
+                                ctx.parser.extra_pending_display = true;
+                            }
                         }
                     }
 
+                    // Bind the streamed asset/dest to what the approved
+                    // memo preview showed for this transfer - otherwise a
+                    // malicious host could display one recipient/asset and
+                    // stream a different one in the transfer body.
+                    if !ctx.parser.asset_dest_checked {
+                        if let Some((asset, dest)) = ctx.parser.pending_asset_dest {
+                            unsafe {
+                                let idx = ctx.parser.transfers_parsed as usize;
+                                let out = &memo_ws_mut().outs[idx];
+                                if dest != out.dest || asset != get_memo_asset(out.asset_index) {
+                                    return Err(AppSW::TxParsingFail);
+                                }
+                            }
+                            ctx.parser.asset_dest_checked = true;
+                        }
+                    }
+
+                    // Once a transfer's extra-data tag has verified, show
+                    // the decrypted memo for confirmation before moving on.
+                    if ctx.parser.extra_pending_display {
+                        ctx.parser.extra_pending_display = false;
+                        if !ui_display_extra_data(&ctx.parser.extra_plaintext)? {
+                            return Err(AppSW::Deny);
+                        }
+                        ctx.parser.extra_plaintext.clear();
+                    }
+
+                    // The canonical bytes (header/transfer/commitment) always
+                    // precede the witness tail (handles + validity proof)
+                    // within a single call's consumed span.
+                    let canonical_len = consumed - witness_consumed;
+                    ctx.tx_hasher
+                        .update(&data[offset..offset + canonical_len])?;
+                    ctx.witness_hasher
+                        .update(&data[offset + canonical_len..offset + consumed])?;
+
                     offset += consumed;
                     ctx.parser.bytes_seen += consumed;
                 }
+
+                // Once every transfer's commitment has been verified, the
+                // remaining stream bytes are the aggregated Bulletproof
+                // range proof covering all outputs at once - witness data.
+                if ctx.parser.transfers_parsed == ctx.parser.transfer_count {
+                    ctx.witness_hasher
+                        .update(&data[offset..])?;
+
+                    // All transfers are in: check every recorded commitment
+                    // at once instead of one-by-one as they streamed.
+                    ctx.verifier.verify_commitments_batched()?;
+
+                    ctx.verifier.ensure_range_proof_started();
+                    for &byte in &data[offset..] {
+                        ctx.verifier.feed_range_proof_byte(byte)?;
+                    }
+                    ctx.parser.bytes_seen += data.len() - offset;
+                    offset = data.len();
+                }
             }
         }
         TX_BURN => {
-            // Parse burn payload - no commitment verification
+            // Parse burn payload - no commitment verification, no witness region
             if ctx.parser.bytes_seen >= 35 && !ctx.parser.burn_parsed {
                 let consumed = ctx.parser.parse_burn(&data[offset..], memo)?;
+                ctx.tx_hasher
+                    .update(&data[offset..offset + consumed])?;
                 offset += consumed;
                 ctx.parser.bytes_seen += consumed;
             } else if ctx.parser.burn_parsed {
@@ -312,10 +596,87 @@ fn parse_and_verify_stream(ctx: &mut TxContext, data: &[u8]) -> Result<(), AppSW
                 }
             }
         }
+        TX_MULTISIG => {
+            if ctx.parser.bytes_seen >= 35 && !ctx.parser.multisig_parsed {
+                let consumed = ctx.parser.parse_multisig(&data[offset..])?;
+                ctx.tx_hasher
+                    .update(&data[offset..offset + consumed])?;
+                offset += consumed;
+                ctx.parser.bytes_seen += consumed;
+
+                if ctx.parser.multisig_pending_display {
+                    ctx.parser.multisig_pending_display = false;
+                    if !ui_display_multisig(
+                        ctx.parser.multisig_threshold,
+                        &ctx.parser.multisig_participants,
+                    )? {
+                        return Err(AppSW::Deny);
+                    }
+                }
+            } else if ctx.parser.multisig_parsed {
+                if offset < data.len() {
+                    return Err(AppSW::TxParsingFail);
+                }
+            }
+        }
+        TX_INVOKE_CONTRACT => {
+            if ctx.parser.bytes_seen >= 35 && !ctx.parser.contract_parsed {
+                let consumed = ctx.parser.parse_contract_call(&data[offset..])?;
+                ctx.tx_hasher
+                    .update(&data[offset..offset + consumed])?;
+                offset += consumed;
+                ctx.parser.bytes_seen += consumed;
+
+                if ctx.parser.contract_pending_display {
+                    ctx.parser.contract_pending_display = false;
+                    let params_hash =
+                        ctx.parser.contract_params_hash.ok_or(AppSW::TxParsingFail)?;
+                    if !ui_display_contract_call(
+                        &ctx.parser.contract_addr,
+                        ctx.parser.contract_entrypoint,
+                        &ctx.parser.contract_deposits,
+                        &params_hash,
+                    )? {
+                        return Err(AppSW::Deny);
+                    }
+                }
+            } else if ctx.parser.contract_parsed {
+                if offset < data.len() {
+                    return Err(AppSW::TxParsingFail);
+                }
+            }
+        }
+        TX_DEPLOY_CONTRACT => {
+            if ctx.parser.bytes_seen >= 35 && !ctx.parser.deploy_parsed {
+                let consumed = ctx.parser.parse_deploy_contract(&data[offset..])?;
+                ctx.tx_hasher
+                    .update(&data[offset..offset + consumed])?;
+                offset += consumed;
+                ctx.parser.bytes_seen += consumed;
+
+                if ctx.parser.deploy_pending_display {
+                    ctx.parser.deploy_pending_display = false;
+                    if !ui_display_deploy_contract(
+                        &ctx.parser.deploy_module_hash,
+                        ctx.parser.deploy_module_size,
+                        &ctx.parser.deploy_deposits,
+                    )? {
+                        return Err(AppSW::Deny);
+                    }
+                }
+            } else if ctx.parser.deploy_parsed {
+                if offset < data.len() {
+                    return Err(AppSW::TxParsingFail);
+                }
+            }
+        }
         _ => {
-            // Other transaction types - just consume bytes
-            offset = data.len();
+            // Other transaction types - not yet split into canonical/witness
+            // regions, so treat the whole chunk as canonical for now.
+            ctx.tx_hasher
+                .update(&data[offset..])?;
             ctx.parser.bytes_seen += data.len() - offset;
+            offset = data.len();
         }
     }
 
@@ -323,10 +684,24 @@ fn parse_and_verify_stream(ctx: &mut TxContext, data: &[u8]) -> Result<(), AppSW
 }
 
 fn finalize_transaction(comm: &mut Comm, ctx: &mut TxContext) -> Result<(), AppSW> {
+    finalize_tx_hash(ctx)?;
+    compute_signature_and_append(comm, ctx)
+}
+
+/// Runs every check that must hold once the whole transaction body has
+/// streamed in - memo-declared balance/witness-digest checks, burn-specific
+/// length check - then finalizes and returns the canonical (signed-region)
+/// hash. Split out of `finalize_transaction` so `handler_multisig_sign` can
+/// derive the same on-device hash a solo signer would, without also
+/// appending a solo-style signature to `comm`.
+pub(crate) fn finalize_tx_hash(ctx: &mut TxContext) -> Result<[u8; 64], AppSW> {
     // Final validation
     if let Some(memo) = &ctx.memo {
         match memo.tx_type {
             TX_TRANSFER => {
+                let net_commitment = memo.net_commitment.ok_or(AppSW::MemoInvalid)?;
+                ctx.verifier.verify_balance(memo.fee, None, &net_commitment)?;
+
                 if !ctx.verifier.all_verified() {
                     return Err(AppSW::InvalidCommitment);
                 }
@@ -344,27 +719,61 @@ fn finalize_transaction(comm: &mut Comm, ctx: &mut TxContext) -> Result<(), AppS
                     return Err(AppSW::TxParsingFail);
                 }
             }
+            TX_MULTISIG => {
+                if !ctx.parser.multisig_parsed {
+                    return Err(AppSW::TxParsingFail);
+                }
+            }
+            TX_INVOKE_CONTRACT => {
+                if !ctx.parser.contract_parsed {
+                    return Err(AppSW::TxParsingFail);
+                }
+            }
+            TX_DEPLOY_CONTRACT => {
+                if !ctx.parser.deploy_parsed {
+                    return Err(AppSW::TxParsingFail);
+                }
+            }
             _ => {}
         }
     }
 
-    // Finalize hash
-    let mut hash = [0u8; 64];
-    ctx.tx_hasher
-        .finalize(&mut hash)
-        .map_err(|_| AppSW::TxSignFail)?;
-    ctx.tx_hash = Some(hash);
+    // Finalize the witness digest and check it against what the host
+    // declared in the memo, if it declared one. The witness region (range
+    // proofs, sigma proofs, signatures) is never part of what gets signed,
+    // but the host's declared digest still has to match what was actually
+    // streamed.
+    let witness_hash = ctx.witness_hasher.finalize()?;
+    ctx.witness_hash = Some(witness_hash);
 
-    // Sign
-    compute_signature_and_append(comm, ctx)
+    if let Some(memo) = &ctx.memo {
+        if let Some(expected) = memo.witness_hash {
+            if expected != witness_hash {
+                return Err(AppSW::InvalidCommitment);
+            }
+        }
+    }
+
+    // Finalize the canonical hash - this, and only this, is what gets signed.
+    let hash = ctx.tx_hasher.finalize().map_err(|_| AppSW::TxSignFail)?;
+    ctx.tx_hash = Some(hash);
+    Ok(hash)
 }
 
 fn compute_signature_and_append(comm: &mut Comm, ctx: &TxContext) -> Result<(), AppSW> {
     let tx_hash = ctx.tx_hash.ok_or(AppSW::TxHashFail)?;
+    sign_hash_and_append(comm, ctx.path.as_ref(), &tx_hash)
+}
 
-    with_derived_key(ctx.path.as_ref(), |private_key, _| {
+/// Derives the signing key for `path`, Schnorr-signs `hash`, and appends the
+/// result as `[len(1)][s(32)][e(32)]` - the same reply shape whether the hash
+/// came from a transaction (`compute_signature_and_append`) or an arbitrary
+/// message (`handler_sign_message`), since both ultimately sign a SHA3-512
+/// digest under this scalar.
+fn sign_hash_and_append(comm: &mut Comm, path: &[u32], hash: &[u8; 64]) -> Result<(), AppSW> {
+    with_derived_key(path, |private_key| {
         let pubkey = xelis_public_from_private(private_key.as_ref())?;
-        let signature = schnorr_sign(private_key.as_ref(), &pubkey, &tx_hash)?;
+        let signature = schnorr_sign(private_key.as_ref(), &pubkey, hash)?;
 
         let sig_bytes = signature.to_le_bytes();
         comm.append(&[64u8]);
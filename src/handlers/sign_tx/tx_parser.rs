@@ -1,5 +1,7 @@
 use crate::{xlb::*, AppSW};
 use alloc::vec::Vec;
+use ledger_device_sdk::hash::sha3::Sha3_512;
+use ledger_device_sdk::hash::HashInit;
 
 pub struct TxStreamParser {
     pub bytes_seen: usize,
@@ -9,14 +11,70 @@ pub struct TxStreamParser {
     pub transfer_count: u8,
     pub transfers_parsed: u8,
     pub pending_tail_skip: usize,
+    tail_total_len: usize,
+    pub sender_handle: [u8; 32],
+    pub receiver_handle: [u8; 32],
+    pub receiver_handle_ready: bool,
     pub partial_buffer: [u8; 256],
     pub partial_len: usize,
     pub partial_type: PartialType,
     pub burn_parsed: bool,
+
+    // Extra-data decryption. The ciphertext+tag are buffered whole during
+    // `PartialType::ExtraData` and only decrypted once the witness tail -
+    // and with it `sender_handle`, this transfer's DH point - has streamed
+    // in, since the handle always arrives after the ciphertext it keys.
+    pub pending_asset_dest: Option<([u8; 32], [u8; 32])>,
+    pub asset_dest_checked: bool,
+    pub extra_ciphertext: Vec<u8>,
+    pub extra_tag: [u8; 16],
+    pub extra_ciphertext_ready: bool,
+    pub extra_plaintext: Vec<u8>,
+    pub extra_pending_display: bool,
+
+    // MultiSig streaming state.
+    pub multisig_threshold: u8,
+    pub multisig_participants_count: u8,
+    pub multisig_participants: Vec<[u8; 32]>,
+    pub multisig_parsed: bool,
+    pub multisig_pending_display: bool,
+
+    // Contract Call streaming state.
+    contract_stage: u8,
+    pub contract_addr: [u8; 32],
+    pub contract_entrypoint: u64,
+    pub contract_deposits_count: u64,
+    pub contract_deposits: Vec<([u8; 32], u64)>,
+    pub contract_params_len: u64,
+    contract_params_read: u64,
+    contract_params_hasher: Option<Sha3_512>,
+    pub contract_params_hash: Option<[u8; 64]>,
+    pub contract_parsed: bool,
+    pub contract_pending_display: bool,
+
+    // Deploy Contract streaming state.
+    deploy_stage: u8,
+    pub deploy_module_hash: [u8; 32],
+    pub deploy_module_size: u64,
+    pub deploy_has_constructor: bool,
+    pub deploy_deposits_count: u64,
+    pub deploy_deposits: Vec<([u8; 32], u64)>,
+    pub deploy_parsed: bool,
+    pub deploy_pending_display: bool,
 }
 
 pub const BURN_V1_LEN: [usize; 2] = [1062, 1382];
 
+/// Cap on how much decrypted `extra_data` is buffered for on-screen display;
+/// the region itself can be larger, but only this much is ever rendered.
+pub const MAX_EXTRA_DATA_DISPLAY: usize = 255;
+
+/// Cap on a transfer's encrypted `extra_data` region (ciphertext + 16-byte
+/// tag), enforced before it is buffered whole awaiting the sender handle -
+/// bounds the buffer the same way `MAX_TRANSACTION_LEN`/`MAX_CHUNKS` bound
+/// the stream it is carried in.
+pub const MAX_EXTRA_DATA_LEN: usize = 512;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PartialType {
     None,
@@ -35,10 +93,45 @@ impl TxStreamParser {
             transfer_count: 0,
             transfers_parsed: 0,
             pending_tail_skip: 0,
+            tail_total_len: 0,
+            sender_handle: [0u8; 32],
+            receiver_handle: [0u8; 32],
+            receiver_handle_ready: false,
             partial_buffer: [0u8; 256],
             partial_len: 0,
             partial_type: PartialType::None,
             burn_parsed: false,
+            pending_asset_dest: None,
+            asset_dest_checked: false,
+            extra_ciphertext: Vec::new(),
+            extra_tag: [0u8; 16],
+            extra_ciphertext_ready: false,
+            extra_plaintext: Vec::new(),
+            extra_pending_display: false,
+            multisig_threshold: 0,
+            multisig_participants_count: 0,
+            multisig_participants: Vec::new(),
+            multisig_parsed: false,
+            multisig_pending_display: false,
+            contract_stage: 0,
+            contract_addr: [0u8; 32],
+            contract_entrypoint: 0,
+            contract_deposits_count: 0,
+            contract_deposits: Vec::new(),
+            contract_params_len: 0,
+            contract_params_read: 0,
+            contract_params_hasher: None,
+            contract_params_hash: None,
+            contract_parsed: false,
+            contract_pending_display: false,
+            deploy_stage: 0,
+            deploy_module_hash: [0u8; 32],
+            deploy_module_size: 0,
+            deploy_has_constructor: false,
+            deploy_deposits_count: 0,
+            deploy_deposits: Vec::new(),
+            deploy_parsed: false,
+            deploy_pending_display: false,
         }
     }
 
@@ -50,10 +143,45 @@ impl TxStreamParser {
         self.transfer_count = 0;
         self.transfers_parsed = 0;
         self.pending_tail_skip = 0;
+        self.tail_total_len = 0;
+        self.sender_handle = [0u8; 32];
+        self.receiver_handle = [0u8; 32];
+        self.receiver_handle_ready = false;
         self.partial_buffer = [0u8; 256];
         self.partial_len = 0;
         self.partial_type = PartialType::None;
         self.burn_parsed = false;
+        self.pending_asset_dest = None;
+        self.asset_dest_checked = false;
+        self.extra_ciphertext.clear();
+        self.extra_tag = [0u8; 16];
+        self.extra_ciphertext_ready = false;
+        self.extra_plaintext.clear();
+        self.extra_pending_display = false;
+        self.multisig_threshold = 0;
+        self.multisig_participants_count = 0;
+        self.multisig_participants.clear();
+        self.multisig_parsed = false;
+        self.multisig_pending_display = false;
+        self.contract_stage = 0;
+        self.contract_addr = [0u8; 32];
+        self.contract_entrypoint = 0;
+        self.contract_deposits_count = 0;
+        self.contract_deposits.clear();
+        self.contract_params_len = 0;
+        self.contract_params_read = 0;
+        self.contract_params_hasher = None;
+        self.contract_params_hash = None;
+        self.contract_parsed = false;
+        self.contract_pending_display = false;
+        self.deploy_stage = 0;
+        self.deploy_module_hash = [0u8; 32];
+        self.deploy_module_size = 0;
+        self.deploy_has_constructor = false;
+        self.deploy_deposits_count = 0;
+        self.deploy_deposits.clear();
+        self.deploy_parsed = false;
+        self.deploy_pending_display = false;
     }
 
     /// Parse transaction header from stream
@@ -156,19 +284,290 @@ impl TxStreamParser {
         Ok(offset)
     }
 
-    /// Extract commitment from transfer data
+    /// Streams a MultiSig body: threshold(1) + participants_count(1) +
+    /// participants_count * pubkey(32). Mirrors `parse_burn`'s style of
+    /// accumulating fixed-size fields through `partial_buffer` rather than
+    /// introducing a dedicated sub-state enum.
+    pub fn parse_multisig(&mut self, data: &[u8]) -> Result<usize, AppSW> {
+        let mut offset = 0;
+
+        if self.partial_len < 2 {
+            while self.partial_len < 2 && offset < data.len() {
+                self.partial_buffer[self.partial_len] = data[offset];
+                self.partial_len += 1;
+                offset += 1;
+            }
+            if self.partial_len < 2 {
+                return Ok(offset);
+            }
+            self.multisig_threshold = self.partial_buffer[0];
+            self.multisig_participants_count = self.partial_buffer[1];
+            self.partial_len = 0; // now counts bytes into the current participant
+        }
+
+        while (self.multisig_participants.len() as u8) < self.multisig_participants_count
+            && offset < data.len()
+        {
+            let needed = 32 - self.partial_len;
+            let available = core::cmp::min(needed, data.len() - offset);
+            self.partial_buffer[self.partial_len..self.partial_len + available]
+                .copy_from_slice(&data[offset..offset + available]);
+            offset += available;
+            self.partial_len += available;
+
+            if self.partial_len == 32 {
+                let mut participant = [0u8; 32];
+                participant.copy_from_slice(&self.partial_buffer[..32]);
+                self.multisig_participants.push(participant);
+                self.partial_len = 0;
+            } else {
+                return Ok(offset);
+            }
+        }
+
+        if (self.multisig_participants.len() as u8) == self.multisig_participants_count {
+            self.multisig_parsed = true;
+            self.multisig_pending_display = true;
+        }
+
+        Ok(offset)
+    }
+
+    /// Streams a Contract Call body: contract(32) + entrypoint_id(varint) +
+    /// deposits_count(varint) + deposits_count * (asset(32) + amount(8)) +
+    /// params_len(varint) + params_len bytes. The parameter blob is never
+    /// buffered - it is hashed as it streams so the review screen can show
+    /// its digest instead.
+    pub fn parse_contract_call(&mut self, data: &[u8]) -> Result<usize, AppSW> {
+        let mut offset = 0;
+
+        loop {
+            match self.contract_stage {
+                0 => {
+                    let needed = 32 - self.partial_len;
+                    let available = core::cmp::min(needed, data.len() - offset);
+                    self.partial_buffer[self.partial_len..self.partial_len + available]
+                        .copy_from_slice(&data[offset..offset + available]);
+                    offset += available;
+                    self.partial_len += available;
+                    if self.partial_len < 32 {
+                        return Ok(offset);
+                    }
+                    self.contract_addr.copy_from_slice(&self.partial_buffer[..32]);
+                    self.partial_len = 0;
+                    self.contract_stage = 1;
+                }
+                1 => {
+                    let (value, consumed) = self.continue_varint(&data[offset..])?;
+                    offset += consumed;
+                    match value {
+                        Some(v) => {
+                            self.contract_entrypoint = v as u64;
+                            self.partial_len = 0;
+                            self.contract_stage = 2;
+                        }
+                        None => return Ok(offset),
+                    }
+                }
+                2 => {
+                    let (value, consumed) = self.continue_varint(&data[offset..])?;
+                    offset += consumed;
+                    match value {
+                        Some(v) => {
+                            self.contract_deposits_count = v as u64;
+                            self.partial_len = 0;
+                            self.contract_stage = 3;
+                        }
+                        None => return Ok(offset),
+                    }
+                }
+                3 => {
+                    if (self.contract_deposits.len() as u64) >= self.contract_deposits_count {
+                        self.contract_stage = 4;
+                        continue;
+                    }
+                    if offset >= data.len() {
+                        return Ok(offset);
+                    }
+                    const DEPOSIT_LEN: usize = 40;
+                    let needed = DEPOSIT_LEN - self.partial_len;
+                    let available = core::cmp::min(needed, data.len() - offset);
+                    self.partial_buffer[self.partial_len..self.partial_len + available]
+                        .copy_from_slice(&data[offset..offset + available]);
+                    offset += available;
+                    self.partial_len += available;
+                    if self.partial_len < DEPOSIT_LEN {
+                        return Ok(offset);
+                    }
+                    let mut asset = [0u8; 32];
+                    asset.copy_from_slice(&self.partial_buffer[..32]);
+                    let amount =
+                        u64::from_be_bytes(self.partial_buffer[32..40].try_into().unwrap());
+                    self.contract_deposits.push((asset, amount));
+                    self.partial_len = 0;
+                }
+                4 => {
+                    let (value, consumed) = self.continue_varint(&data[offset..])?;
+                    offset += consumed;
+                    match value {
+                        Some(v) => {
+                            self.contract_params_len = v as u64;
+                            self.contract_params_read = 0;
+                            self.partial_len = 0;
+                            self.contract_params_hasher = Some(Sha3_512::new());
+                            self.contract_stage = 5;
+                        }
+                        None => return Ok(offset),
+                    }
+                }
+                5 => {
+                    let remaining = (self.contract_params_len - self.contract_params_read) as usize;
+                    let available = core::cmp::min(remaining, data.len() - offset);
+                    if available > 0 {
+                        self.contract_params_hasher
+                            .as_mut()
+                            .ok_or(AppSW::TxParsingFail)?
+                            .update(&data[offset..offset + available])
+                            .map_err(|_| AppSW::TxParsingFail)?;
+                    }
+                    offset += available;
+                    self.contract_params_read += available as u64;
+
+                    if self.contract_params_read < self.contract_params_len {
+                        return Ok(offset);
+                    }
+
+                    let mut digest = [0u8; 64];
+                    self.contract_params_hasher
+                        .take()
+                        .ok_or(AppSW::TxParsingFail)?
+                        .finalize(&mut digest)
+                        .map_err(|_| AppSW::TxParsingFail)?;
+                    self.contract_params_hash = Some(digest);
+                    self.contract_parsed = true;
+                    self.contract_pending_display = true;
+                    self.contract_stage = 6;
+                    return Ok(offset);
+                }
+                _ => return Ok(offset),
+            }
+            // No early return on exhausted input here: every stage returns
+            // on its own when it needs more bytes, and stage 5 must still run
+            // to completion for an empty parameter blob.
+        }
+    }
+
+    /// Streams a Deploy Contract body: module_hash(32) + module_size(varint)
+    /// + has_constructor(1) + [if set: deposits_count(varint) +
+    /// deposits_count * (asset(32) + amount(8))].
+    pub fn parse_deploy_contract(&mut self, data: &[u8]) -> Result<usize, AppSW> {
+        let mut offset = 0;
+
+        loop {
+            match self.deploy_stage {
+                0 => {
+                    let needed = 32 - self.partial_len;
+                    let available = core::cmp::min(needed, data.len() - offset);
+                    self.partial_buffer[self.partial_len..self.partial_len + available]
+                        .copy_from_slice(&data[offset..offset + available]);
+                    offset += available;
+                    self.partial_len += available;
+                    if self.partial_len < 32 {
+                        return Ok(offset);
+                    }
+                    self.deploy_module_hash
+                        .copy_from_slice(&self.partial_buffer[..32]);
+                    self.partial_len = 0;
+                    self.deploy_stage = 1;
+                }
+                1 => {
+                    let (value, consumed) = self.continue_varint(&data[offset..])?;
+                    offset += consumed;
+                    match value {
+                        Some(v) => {
+                            self.deploy_module_size = v as u64;
+                            self.partial_len = 0;
+                            self.deploy_stage = 2;
+                        }
+                        None => return Ok(offset),
+                    }
+                }
+                2 => {
+                    if offset >= data.len() {
+                        return Ok(offset);
+                    }
+                    self.deploy_has_constructor = data[offset] == 1;
+                    offset += 1;
+                    self.deploy_stage = if self.deploy_has_constructor { 3 } else { 5 };
+                }
+                3 => {
+                    let (value, consumed) = self.continue_varint(&data[offset..])?;
+                    offset += consumed;
+                    match value {
+                        Some(v) => {
+                            self.deploy_deposits_count = v as u64;
+                            self.partial_len = 0;
+                            self.deploy_stage = 4;
+                        }
+                        None => return Ok(offset),
+                    }
+                }
+                4 => {
+                    if (self.deploy_deposits.len() as u64) >= self.deploy_deposits_count {
+                        self.deploy_stage = 5;
+                        continue;
+                    }
+                    if offset >= data.len() {
+                        return Ok(offset);
+                    }
+                    const DEPOSIT_LEN: usize = 40;
+                    let needed = DEPOSIT_LEN - self.partial_len;
+                    let available = core::cmp::min(needed, data.len() - offset);
+                    self.partial_buffer[self.partial_len..self.partial_len + available]
+                        .copy_from_slice(&data[offset..offset + available]);
+                    offset += available;
+                    self.partial_len += available;
+                    if self.partial_len < DEPOSIT_LEN {
+                        return Ok(offset);
+                    }
+                    let mut asset = [0u8; 32];
+                    asset.copy_from_slice(&self.partial_buffer[..32]);
+                    let amount =
+                        u64::from_be_bytes(self.partial_buffer[32..40].try_into().unwrap());
+                    self.deploy_deposits.push((asset, amount));
+                    self.partial_len = 0;
+                }
+                5 => {
+                    self.deploy_parsed = true;
+                    self.deploy_pending_display = true;
+                    self.deploy_stage = 6;
+                    return Ok(offset);
+                }
+                _ => return Ok(offset),
+            }
+            // No early return on exhausted input here: every stage returns
+            // on its own when it needs more bytes, and stage 5 needs none.
+        }
+    }
+
+    /// Extract commitment from transfer data.
+    ///
+    /// Returns `(commitment, consumed, witness_consumed)`: `witness_consumed`
+    /// is the portion of `consumed` that belongs to the per-transfer witness
+    /// tail (sender/receiver handles + ciphertext validity proof) rather than
+    /// the canonical signed region, so the caller can route each byte range
+    /// to the right hash.
     pub fn extract_commitment_from_transfer(
         &mut self,
         data: &[u8],
-    ) -> Result<(Option<[u8; 32]>, usize), AppSW> {
+    ) -> Result<(Option<[u8; 32]>, usize, usize), AppSW> {
         let mut consumed = 0;
         let mut off = 0;
 
-        // Handle pending tail skip first
+        // Handle pending tail skip first - this is always witness data.
         if self.pending_tail_skip > 0 {
-            let take = core::cmp::min(self.pending_tail_skip, data.len());
-            self.pending_tail_skip -= take;
-            return Ok((None, take));
+            let take = self.consume_tail(data);
+            return Ok((None, take, take));
         }
 
         // Main processing loop
@@ -189,23 +588,33 @@ impl TxStreamParser {
 
                         if self.partial_len < 65 {
                             // Still need more data for header
-                            return Ok((None, consumed));
+                            return Ok((None, consumed, 0));
                         }
                     }
 
                     // Now we have asset(32) + dest(32) + has_extra(1)
                     let has_extra = self.partial_buffer[64];
+                    let mut asset = [0u8; 32];
+                    asset.copy_from_slice(&self.partial_buffer[..32]);
+                    let mut dest = [0u8; 32];
+                    dest.copy_from_slice(&self.partial_buffer[32..64]);
                     self.partial_len = 0; // Reset for next component
 
+                    self.pending_asset_dest = Some((asset, dest));
+                    self.asset_dest_checked = false;
+
                     if has_extra == 1 {
                         // Move to reading extra length
                         self.partial_type = PartialType::ExtraLength;
-                        // Continue in next iteration
                     } else {
                         // No extra data, move directly to commitment
                         self.partial_type = PartialType::Commitment;
-                        // Continue in next iteration
                     }
+
+                    // Pause here so the caller can cross-check asset/dest
+                    // against the memo before any extra-data ciphertext (if
+                    // `has_extra`) is buffered.
+                    return Ok((None, consumed, 0));
                 }
 
                 PartialType::ExtraLength => {
@@ -229,7 +638,11 @@ impl TxStreamParser {
                             self.partial_len = 0; // Reset for next component
 
                             if extra_len > 0 {
+                                if extra_len > MAX_EXTRA_DATA_LEN {
+                                    return Err(AppSW::TxParsingFail);
+                                }
                                 self.partial_type = PartialType::ExtraData(extra_len);
+                                self.extra_ciphertext.clear();
                             } else {
                                 // Zero-length extra, move to commitment
                                 self.partial_type = PartialType::Commitment;
@@ -240,29 +653,47 @@ impl TxStreamParser {
 
                     if off == start_off || self.partial_type == PartialType::ExtraLength {
                         // No progress made or still reading varint
-                        return Ok((None, consumed));
+                        return Ok((None, consumed, 0));
                     }
                     // Continue to next state
                 }
 
                 PartialType::ExtraData(total_len) => {
-                    // Skip extra data (we don't validate it)
-                    let remaining = total_len - self.partial_len;
-                    let available = core::cmp::min(remaining, data.len() - off);
+                    // The last 16 bytes of the region are the Poly1305 tag;
+                    // everything before that is ciphertext. Both are only
+                    // buffered here - decryption needs `sender_handle`,
+                    // which does not arrive until this transfer's witness
+                    // tail streams in, well after the ciphertext does.
+                    if total_len < 16 {
+                        return Err(AppSW::TxParsingFail);
+                    }
+                    let ciphertext_len = total_len - 16;
 
-                    off += available;
-                    consumed += available;
-                    self.partial_len += available;
+                    while self.partial_len < total_len && off < data.len() {
+                        let byte = data[off];
+                        off += 1;
+                        consumed += 1;
 
-                    if self.partial_len >= total_len {
-                        // Done with extra data, move to commitment
-                        self.partial_type = PartialType::Commitment;
-                        self.partial_len = 0; // Reset for commitment
-                                              // Continue in next iteration
-                    } else {
-                        // Still skipping extra data
-                        return Ok((None, consumed));
+                        if self.partial_len < ciphertext_len {
+                            self.extra_ciphertext.push(byte);
+                        } else {
+                            self.partial_buffer[self.partial_len - ciphertext_len] = byte;
+                        }
+                        self.partial_len += 1;
+                    }
+
+                    if self.partial_len < total_len {
+                        // Still streaming extra data
+                        return Ok((None, consumed, 0));
                     }
+
+                    self.extra_tag.copy_from_slice(&self.partial_buffer[..16]);
+                    self.extra_ciphertext_ready = true;
+
+                    // Done with extra data, move to commitment
+                    self.partial_type = PartialType::Commitment;
+                    self.partial_len = 0; // Reset for commitment
+                                          // Continue in next iteration
                 }
 
                 PartialType::Commitment => {
@@ -286,31 +717,63 @@ impl TxStreamParser {
                         self.partial_type = PartialType::None;
                         self.partial_len = 0;
 
-                        // Calculate and handle tail bytes to skip
-                        let tail_len = transfer_tail_len_after_commit(self.tx_version);
-                        let have = data.len().saturating_sub(off);
-                        let skip_now = core::cmp::min(tail_len, have);
+                        // The tail holds sender_handle(32) + receiver_handle(32)
+                        // + the ciphertext validity proof; start tracking it so
+                        // `consume_tail` can pull the receiver handle out of the
+                        // bytes it otherwise just skips.
+                        self.tail_total_len = transfer_tail_len_after_commit(self.tx_version);
+                        self.pending_tail_skip = self.tail_total_len;
+                        self.receiver_handle_ready = false;
+                        let skip_now = self.consume_tail(&data[off..]);
                         off += skip_now;
                         consumed += skip_now;
-                        self.pending_tail_skip = tail_len - skip_now;
 
                         self.transfers_parsed += 1;
 
-                        return Ok((Some(commitment), consumed));
+                        // The commitment itself is canonical (signed); the
+                        // tail bytes just skipped are witness data.
+                        return Ok((Some(commitment), consumed, skip_now));
                     } else {
                         // Still reading commitment
-                        return Ok((None, consumed));
+                        return Ok((None, consumed, 0));
                     }
                 }
             }
 
             // Check if we've consumed all available data
             if off >= data.len() {
-                return Ok((None, consumed));
+                return Ok((None, consumed, 0));
             }
         }
     }
 
+    /// Consumes as much of the remaining witness tail as `data` holds,
+    /// capturing the sender handle (tail bytes `[0, 32)`) into
+    /// `sender_handle` and the receiver handle (tail bytes `[32, 64)`) into
+    /// `receiver_handle` as they pass by instead of discarding them
+    /// outright. Sets `receiver_handle_ready` once the whole tail - and
+    /// therefore both handles - has streamed in.
+    fn consume_tail(&mut self, data: &[u8]) -> usize {
+        let take = core::cmp::min(self.pending_tail_skip, data.len());
+        let tail_offset = self.tail_total_len - self.pending_tail_skip;
+
+        for i in 0..take {
+            let pos = tail_offset + i;
+            if (0..32).contains(&pos) {
+                self.sender_handle[pos] = data[i];
+            } else if (32..64).contains(&pos) {
+                self.receiver_handle[pos - 32] = data[i];
+            }
+        }
+
+        self.pending_tail_skip -= take;
+        if self.pending_tail_skip == 0 {
+            self.receiver_handle_ready = true;
+        }
+
+        take
+    }
+
     fn continue_varint(&mut self, data: &[u8]) -> Result<(Option<usize>, usize), AppSW> {
         let mut consumed = 0;
 
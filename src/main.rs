@@ -31,8 +31,11 @@ mod app_ui {
 mod handlers {
     #[cfg(debug_assertions)]
     pub mod debug_keys;
+    pub mod get_address;
     pub mod get_public_key;
     pub mod get_version;
+    pub mod get_xpub;
+    pub mod multisig_sign;
     pub mod sign_tx;
 }
 
@@ -85,10 +88,14 @@ pub enum AppSW {
     InvalidCommitment = 0xC000,
     BlindersRequired = 0xC001,
     InvalidCompressedRistretto = 0xC002,
+    ExtraDataAuthFail = 0xC003,
+    MultiSigAlreadySigned = 0xC004,
+    MultiSigThresholdMet = 0xC005,
     Ok = 0x9000,
     CryptoError = 0x6F00,
     AddressError = 0x6F01,
     ParamError = 0x6F02,
+    AddressMismatch = 0x6F03,
 }
 
 impl From<AppSW> for Reply {
@@ -104,15 +111,27 @@ pub enum Instruction {
     GetPubkey {
         display: bool,
     },
+    GetAddress {
+        confirm: bool,
+    },
+    GetXpub,
     SignTx {
         chunk: u8,
         more: bool,
     },
+    SignMessage {
+        chunk: u8,
+        more: bool,
+    },
     LoadMemo {
         chunk: u8,
         more: bool,
     },
     SendBlinders,
+    MultiSigSign {
+        chunk: u8,
+        more: bool,
+    },
     #[cfg(debug_assertions)]
     DebugTestKeys,
 }
@@ -138,6 +157,10 @@ impl TryFrom<ApduHeader> for Instruction {
             (5, 0 | 1, 0) => Ok(Instruction::GetPubkey {
                 display: value.p1 != 0,
             }),
+            (0x11, 0 | 1, 0) => Ok(Instruction::GetAddress {
+                confirm: value.p1 != 0,
+            }),
+            (0x14, 0, 0) => Ok(Instruction::GetXpub),
             (6, P1_CHUNK_FIRST, P2_MORE_DATA)
             | (6, 1..=P1_CHUNK_MAX, P2_CHUNK_LAST | P2_MORE_DATA) => Ok(Instruction::SignTx {
                 chunk: value.p1,
@@ -150,9 +173,23 @@ impl TryFrom<ApduHeader> for Instruction {
                 })
             }
             (0x12, _, _) => Ok(Instruction::SendBlinders),
+            (0x15, P1_CHUNK_FIRST, P2_MORE_DATA)
+            | (0x15, 1..=P1_CHUNK_MAX, P2_CHUNK_LAST | P2_MORE_DATA) => {
+                Ok(Instruction::MultiSigSign {
+                    chunk: value.p1,
+                    more: value.p2 == P2_MORE_DATA,
+                })
+            }
+            (0x13, P1_CHUNK_FIRST, P2_MORE_DATA)
+            | (0x13, 1..=P1_CHUNK_MAX, P2_CHUNK_LAST | P2_MORE_DATA) => {
+                Ok(Instruction::SignMessage {
+                    chunk: value.p1,
+                    more: value.p2 == P2_MORE_DATA,
+                })
+            }
             #[cfg(debug_assertions)]
             (0xF0, _, _) => Ok(Instruction::DebugTestKeys),
-            (3..=6 | 0x10 | 0x12, _, _) => Err(AppSW::WrongP1P2),
+            (3..=6 | 0x10..=0x15, _, _) => Err(AppSW::WrongP1P2),
             (_, _, _) => Err(AppSW::InsNotSupported),
         }
     }
@@ -232,6 +269,51 @@ pub fn show_status_and_home_if_needed(
             }
         }
 
+        // Multisig cosign step: same shape as solo signing, since it streams
+        // and hashes the same real transaction body.
+        Instruction::MultiSigSign { .. } => {
+            if ctx.sign_completed {
+                let ok = (status == AppSW::Ok) && ctx.sign_succeeded;
+                Action::Status {
+                    ok,
+                    ty: StatusType::Transaction,
+                    go_home: true,
+                    reset: true,
+                }
+            } else if status != AppSW::Ok {
+                Action::Status {
+                    ok: false,
+                    ty: StatusType::Transaction,
+                    go_home: true,
+                    reset: true,
+                }
+            } else {
+                Action::Nothing
+            }
+        }
+
+        // Message-signing step:
+        Instruction::SignMessage { .. } => {
+            if ctx.msg_sign_completed {
+                let ok = (status == AppSW::Ok) && ctx.msg_sign_succeeded;
+                Action::Status {
+                    ok,
+                    ty: StatusType::Transaction,
+                    go_home: true,
+                    reset: true,
+                }
+            } else if status != AppSW::Ok {
+                Action::Status {
+                    ok: false,
+                    ty: StatusType::Transaction,
+                    go_home: true,
+                    reset: true,
+                }
+            } else {
+                Action::Nothing
+            }
+        }
+
         // Address display:
         Instruction::GetPubkey { display: true }
             if status == AppSW::Ok || status == AppSW::Deny =>
@@ -304,7 +386,11 @@ extern "C" fn sample_main() {
 fn handle_apdu(comm: &mut Comm, ins: &Instruction, ctx: &mut TxContext) -> Result<(), AppSW> {
     if !matches!(
         ins,
-        Instruction::SignTx { .. } | Instruction::LoadMemo { .. } | Instruction::SendBlinders
+        Instruction::SignTx { .. }
+            | Instruction::SignMessage { .. }
+            | Instruction::LoadMemo { .. }
+            | Instruction::SendBlinders
+            | Instruction::MultiSigSign { .. }
     ) {
         ctx.reset();
     }
@@ -316,12 +402,22 @@ fn handle_apdu(comm: &mut Comm, ins: &Instruction, ctx: &mut TxContext) -> Resul
         }
         Instruction::GetVersion => handler_get_version(comm),
         Instruction::GetPubkey { display } => handler_get_public_key(comm, *display),
+        Instruction::GetAddress { confirm } => {
+            handlers::get_address::handler_get_address(comm, *confirm)
+        }
+        Instruction::GetXpub => handlers::get_xpub::handler_get_xpub(comm),
         Instruction::SignTx { chunk, more } => handler_sign_tx(comm, *chunk, *more, ctx),
+        Instruction::SignMessage { chunk, more } => {
+            handlers::sign_tx::handler_sign_message(comm, *chunk, *more, ctx)
+        }
         #[cfg(debug_assertions)]
         Instruction::DebugTestKeys => handlers::debug_keys::handler_debug_keys(comm),
         Instruction::LoadMemo { chunk, more } => {
             handlers::sign_tx::handler_load_memo(comm, *chunk, *more, ctx)
         }
         Instruction::SendBlinders => handlers::sign_tx::handler_send_blinders(comm, ctx),
+        Instruction::MultiSigSign { chunk, more } => {
+            handlers::multisig_sign::handler_multisig_sign(comm, *chunk, *more, ctx)
+        }
     }
 }
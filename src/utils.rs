@@ -1,10 +1,19 @@
-use alloc::string::String;
+use alloc::format;
+use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
 
 use crate::AppSW;
 use ledger_device_sdk::ecc::CxError;
 
+/// Maximum derivation depth accepted from the host - generous enough for
+/// `m/44'/<coin>'/<account>'/<change>/<index>` plus a few extra levels, but
+/// bounded so a host can't make the device churn through an unbounded
+/// derivation chain.
+pub const MAX_BIP32_PATH_DEPTH: usize = 10;
+
+const HARDENED_BIT: u32 = 0x8000_0000;
+
 /// BIP32 path stored as an array of [`u32`].
 #[derive(Default)]
 pub struct Bip32Path(Vec<u32>);
@@ -15,6 +24,24 @@ impl AsRef<[u32]> for Bip32Path {
     }
 }
 
+impl Bip32Path {
+    /// Validates this path's depth and that it begins with a hardened
+    /// purpose level, without assuming any fixed account layout beyond
+    /// that - callers that need a full watch-only xpub take whatever
+    /// depth the host asks for.
+    pub fn validate(&self) -> Result<(), AppSW> {
+        if self.0.is_empty() || self.0.len() > MAX_BIP32_PATH_DEPTH {
+            return Err(AppSW::ParamError);
+        }
+
+        if self.0[0] & HARDENED_BIT == 0 {
+            return Err(AppSW::ParamError);
+        }
+
+        Ok(())
+    }
+}
+
 impl TryFrom<&[u8]> for Bip32Path {
     type Error = AppSW;
 
@@ -81,3 +108,41 @@ pub fn to_hex_string_upper(data: &[u8]) -> String {
     to_hex_upper(data, &mut buf);
     unsafe { String::from_utf8_unchecked(buf) }
 }
+
+/// Render a raw atomic `u64` amount as a fixed-point string, inserting the
+/// decimal point `decimals` digits from the right and left-padding the
+/// integer part with zeros when it is shorter than `decimals`.
+pub fn format_fixed_point(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+
+    let digits = amount.to_string();
+    let decimals = decimals as usize;
+
+    if digits.len() <= decimals {
+        let mut fraction = String::with_capacity(decimals);
+        for _ in 0..(decimals - digits.len()) {
+            fraction.push('0');
+        }
+        fraction.push_str(&digits);
+        format!("0.{fraction}")
+    } else {
+        let split = digits.len() - decimals;
+        format!("{}.{}", &digits[..split], &digits[split..])
+    }
+}
+
+/// Same as [`format_fixed_point`] but drops trailing fractional zeros (and
+/// the decimal point itself when the fraction is empty). Used where screen
+/// space is tight.
+pub fn format_fixed_point_trimmed(amount: u64, decimals: u8) -> String {
+    let full = format_fixed_point(amount, decimals);
+    if !full.contains('.') {
+        return full;
+    }
+
+    let trimmed = full.trim_end_matches('0');
+    let trimmed = trimmed.trim_end_matches('.');
+    trimmed.to_string()
+}
@@ -17,27 +17,72 @@ pub const TAG_ASSET_TABLE: u8 = 0x04;  // New: asset table
 pub const TAG_OUT_COUNT: u8 = 0x10;
 pub const TAG_OUT_ITEM: u8 = 0x20;
 pub const TAG_BURN: u8 = 0x30;
+// Host-declared SHA3-512 digest of the witness region (range proofs, sigma
+// proofs, signatures) the device streams but does not sign. Optional: only
+// present for tx types that carry a witness region.
+pub const TAG_WITNESS_HASH: u8 = 0x40;
+// Host-declared Pedersen commitment to the transaction's net spend
+// (Σ output commitments + fee·G + burn·G), checked against the same sum the
+// device accumulates from the output commitments it verifies on the wire.
+// Required for TX_TRANSFER so every approved amount is bound to the actual
+// value leaving the sender, not just individually well-formed.
+pub const TAG_NET_COMMITMENT: u8 = 0x41;
+// TX_INVOKE_CONTRACT preview: target contract hash, entrypoint selector,
+// deposits (reusing the asset-table indexing from TAG_ASSET_TABLE), and a
+// bounded human-readable parameter preview for display only.
+pub const TAG_INVOKE: u8 = 0x50;
+// TX_DEPLOY_CONTRACT preview: bytecode length/commitment and constructor
+// deposits.
+pub const TAG_DEPLOY: u8 = 0x51;
+// TX_MULTISIG preview: signing threshold and the full participant public-key
+// set, so a multisig wallet setup/co-sign can be confirmed from the memo
+// alone rather than being a zero-output no-op.
+pub const TAG_MULTISIG: u8 = 0x52;
 
 // Native asset is always index 0 (not stored in table)
 pub const NATIVE_ASSET_INDEX: u8 = 0;
 pub const NATIVE_ASSET: [u8; 32] = [0u8; 32];
+pub const NATIVE_ASSET_DECIMALS: u8 = 8;
+pub const NATIVE_ASSET_TICKER: &str = "XEL";
 
 use core::mem::MaybeUninit;
 
+/// Per-asset display metadata carried alongside the raw 32-byte asset hash,
+/// so amounts can be rendered as fixed-point values instead of raw atomic units.
+#[derive(Clone, Debug)]
+pub struct AssetInfo {
+    pub asset: [u8; 32],
+    pub decimals: u8,
+    pub ticker: Vec<u8>,
+}
+
 pub struct MemoWorkspace {
-    pub asset_table: Vec<[u8; 32]>,
+    pub asset_table: Vec<AssetInfo>,
     pub outs: Vec<MemoOut>,
     pub burn: Option<MemoBurn>,
+    pub invoke: Option<MemoInvoke>,
+    pub deploy: Option<MemoDeploy>,
+    pub multisig: Option<MemoMultisig>,
 }
 
 impl MemoWorkspace {
     #[inline] fn new() -> Self {
-        Self { asset_table: Vec::new(), outs: Vec::new(), burn: None }
+        Self {
+            asset_table: Vec::new(),
+            outs: Vec::new(),
+            burn: None,
+            invoke: None,
+            deploy: None,
+            multisig: None,
+        }
     }
     #[inline] pub fn clear(&mut self) {
         self.asset_table.clear();
         self.outs.clear();
         self.burn = None;
+        self.invoke = None;
+        self.deploy = None;
+        self.multisig = None;
     }
 }
 
@@ -65,7 +110,7 @@ pub fn get_memo_asset(index: u8) -> [u8; 32] {
             // Index 1 maps to asset_table[0], 2 to [1], etc.
             let table_idx = (index as usize).saturating_sub(1);
             if table_idx < ws.asset_table.len() {
-                ws.asset_table[table_idx]
+                ws.asset_table[table_idx].asset
             } else {
                 // Shouldn't happen with valid memo
                 NATIVE_ASSET
@@ -74,6 +119,24 @@ pub fn get_memo_asset(index: u8) -> [u8; 32] {
     }
 }
 
+/// Decimals + ticker to use when rendering an amount for the given asset index.
+pub fn get_memo_asset_display(index: u8) -> (u8, Vec<u8>) {
+    unsafe {
+        let ws = memo_ws_mut();
+        if index == NATIVE_ASSET_INDEX {
+            (NATIVE_ASSET_DECIMALS, NATIVE_ASSET_TICKER.as_bytes().to_vec())
+        } else {
+            let table_idx = (index as usize).saturating_sub(1);
+            if table_idx < ws.asset_table.len() {
+                let info = &ws.asset_table[table_idx];
+                (info.decimals, info.ticker.clone())
+            } else {
+                (NATIVE_ASSET_DECIMALS, NATIVE_ASSET_TICKER.as_bytes().to_vec())
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MemoOut {
     pub asset_index: u8,  // Index into asset table (0 = native)
@@ -88,6 +151,13 @@ pub struct MemoPreview {
     pub tx_type: u8,
     pub fee: u64,
     pub nonce: u64,
+    // Host-declared digest of the witness region, checked against what the
+    // device actually streamed once `finalize_transaction` has both hashes.
+    pub witness_hash: Option<[u8; 64]>,
+    // Host-declared net-spend commitment, checked against the device's own
+    // running sum of verified output commitments (see
+    // `CommitmentVerifier::verify_balance`).
+    pub net_commitment: Option<[u8; 32]>,
 }
 
 #[derive(Clone, Debug)]
@@ -96,178 +166,502 @@ pub struct MemoBurn {
     pub amount: u64,
 }
 
-/// Read unsigned LEB128 (u64).
-fn read_leb128(buf: &[u8], mut off: usize) -> Result<(u64, usize), AppSW> {
-    let mut val: u64 = 0;
-    let mut shift = 0;
+/// One deposit attached to a contract call or deployment, indexed into
+/// `MemoWorkspace::asset_table` the same way `MemoOut::asset_index` is, so
+/// `get_memo_asset`/`get_memo_asset_display` keep working unchanged.
+#[derive(Clone, Debug)]
+pub struct MemoDeposit {
+    pub asset_index: u8,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct MemoInvoke {
+    pub contract: [u8; 32],
+    pub entrypoint: u64,
+    pub deposits: Vec<MemoDeposit>,
+    // Bounded human-readable parameter preview; display only, never hashed.
+    pub params_preview: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MemoDeploy {
+    pub module_size: u64,
+    pub module_commitment: [u8; 32],
+    pub deposits: Vec<MemoDeposit>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MemoMultisig {
+    pub threshold: u8,
+    pub participants: Vec<[u8; 32]>,
+}
+
+/// Cap on the display-only parameter preview carried for `TAG_INVOKE`.
+pub const MAX_INVOKE_PARAMS_PREVIEW_LEN: usize = 255;
+
+/// Parse a `deposits_count(varint) + deposits_count * (asset_index(1) +
+/// amount(8))` sub-table shared by `TAG_INVOKE` and `TAG_DEPLOY`, validating
+/// each `asset_index` against the already-parsed asset table.
+fn parse_deposits(val: &[u8], mut p: usize, asset_table_len: usize) -> Result<(Vec<MemoDeposit>, usize), AppSW> {
+    let (count, pn) = read_leb128(val, p)?;
+    p = pn;
+
+    let mut deposits = Vec::new();
+    for _ in 0..count {
+        if p + 1 + 8 > val.len() {
+            return Err(AppSW::MemoInvalid);
+        }
+        let asset_index = val[p];
+        p += 1;
+        if asset_index > 0 && (asset_index as usize) > asset_table_len {
+            return Err(AppSW::MemoInvalid);
+        }
+        let amount = u64::from_le_bytes(val[p..p + 8].try_into().unwrap());
+        p += 8;
+        deposits.push(MemoDeposit { asset_index, amount });
+    }
+
+    Ok((deposits, p))
+}
+
+/// Read unsigned LEB128 (u64) from a buffer that is already known to hold the
+/// whole varint (e.g. a fully-buffered single TLV record). Thin wrapper over
+/// [`read_leb128_resumable`] that turns "ran out of bytes" into an error
+/// instead of a resumable state, for callers that can't span a chunk
+/// boundary anyway.
+fn read_leb128(buf: &[u8], off: usize) -> Result<(u64, usize), AppSW> {
+    match read_leb128_resumable(buf, off, 0, 0)? {
+        Leb128Step::Done(val, noff) => Ok((val, noff)),
+        Leb128Step::NeedMore { .. } => Err(AppSW::TxParsingFail),
+    }
+}
+
+/// Result of resuming a LEB128 read against whatever bytes are currently
+/// available: either the value (and the offset just past it), or the partial
+/// accumulator/shift to carry over to the next chunk.
+enum Leb128Step {
+    Done(u64, usize),
+    NeedMore { partial: u64, shift: u32 },
+}
+
+/// Read an unsigned LEB128 starting from `(partial, shift)` - zero for a
+/// fresh varint, or whatever a previous call returned in `NeedMore` - so a
+/// multi-byte varint can be resumed across APDU chunk boundaries instead of
+/// failing once `buf` runs out.
+fn read_leb128_resumable(
+    buf: &[u8],
+    mut off: usize,
+    mut partial: u64,
+    mut shift: u32,
+) -> Result<Leb128Step, AppSW> {
     loop {
         if off >= buf.len() {
-            return Err(AppSW::TxParsingFail);
+            return Ok(Leb128Step::NeedMore { partial, shift });
         }
         let b = buf[off];
         off += 1;
-        val |= ((b & 0x7F) as u64) << shift;
+        partial |= ((b & 0x7F) as u64) << shift;
         if (b & 0x80) == 0 {
-            break;
+            return Ok(Leb128Step::Done(partial, off));
         }
         shift += 7;
         if shift >= 64 {
             return Err(AppSW::TxParsingFail);
         }
     }
-    Ok((val, off))
 }
 
-/// Parse memo TLV with asset table optimization
-pub fn parse_memo_tlv(memo: &[u8]) -> Result<MemoPreview, AppSW> {
-    let mut off = 0usize;
-    let mut tx_type = 0u8;
-    let mut fee = 0u64;
-    let mut nonce = 0u64;
-    let mut expected_outs: Option<u64> = None;
+/// Cap on a single TLV record's declared length. Bounds the one record
+/// [`MemoStreamParser`] ever buffers, in place of the old whole-memo
+/// `MAX_MEMO_SIZE` scratch buffer.
+pub const MAX_MEMO_RECORD_LEN: usize = 4 * 1024;
 
-    let ws = memo_ws_mut();
+#[derive(Clone, Copy, PartialEq)]
+enum MemoParseStage {
+    // Waiting for the next record's tag byte.
+    Tag,
+    // Reading `TAG_OUT_COUNT`'s varint, which (uniquely) has no length prefix.
+    OutCountVarint,
+    // Reading the current record's length varint.
+    LenVarint,
+    // Accumulating the current record's value bytes into `record_buf`.
+    Value,
+}
 
-    unsafe {
-        ws.clear();
-        while off < memo.len() {
-            let tag = memo[off];
-            off += 1;
-
-            // Special handling for TAG_OUT_COUNT (no length field)
-            if tag == TAG_OUT_COUNT {
-                let (n, noff) = read_leb128(memo, off)?;
-                off = noff;
-                expected_outs = Some(n);
-                continue;
-            }
+/// Resumable XLB1 TLV parser: consumes `load_memo` chunks as they arrive
+/// instead of buffering the whole memo first. At most one in-progress TLV
+/// record (its value bytes) is ever held in memory; once a record completes
+/// it is dispatched straight into `MemoWorkspace` and the buffer is reused
+/// for the next one.
+pub struct MemoStreamParser {
+    stage: MemoParseStage,
+    tag: u8,
+    varint_partial: u64,
+    varint_shift: u32,
+    record_len: usize,
+    record_buf: Vec<u8>,
+    expected_outs: Option<u64>,
+    tx_type: u8,
+    fee: u64,
+    nonce: u64,
+    witness_hash: Option<[u8; 64]>,
+    net_commitment: Option<[u8; 32]>,
+}
 
-            let (len, noff) = read_leb128(memo, off)?;
-            off = noff;
-            if off + (len as usize) > memo.len() {
-                return Err(AppSW::TxParsingFail);
-            }
-            let val = &memo[off..off + (len as usize)];
-            off += len as usize;
+impl MemoStreamParser {
+    pub fn new() -> Self {
+        Self {
+            stage: MemoParseStage::Tag,
+            tag: 0,
+            varint_partial: 0,
+            varint_shift: 0,
+            record_len: 0,
+            record_buf: Vec::new(),
+            expected_outs: None,
+            tx_type: 0,
+            fee: 0,
+            nonce: 0,
+            witness_hash: None,
+            net_commitment: None,
+        }
+    }
 
-            match tag {
-                TAG_TX_TYPE => {
-                    if val.len() != 1 {
-                        return Err(AppSW::MemoInvalid);
-                    }
-                    tx_type = val[0];
+    /// Clears all parse state and the shared `MemoWorkspace`, ready for a
+    /// fresh memo (called on `load_memo`'s first chunk).
+    pub fn reset(&mut self) {
+        *self = Self::new();
+        memo_ws_mut().clear();
+    }
+
+    /// Feeds the next chunk of raw memo bytes into the state machine,
+    /// advancing through as many complete tag/length/value cycles as `data`
+    /// covers and dispatching each completed record into `MemoWorkspace`
+    /// immediately.
+    pub fn feed(&mut self, mut data: &[u8]) -> Result<(), AppSW> {
+        while !data.is_empty() {
+            match self.stage {
+                MemoParseStage::Tag => {
+                    self.tag = data[0];
+                    data = &data[1..];
+                    self.varint_partial = 0;
+                    self.varint_shift = 0;
+                    self.stage = if self.tag == TAG_OUT_COUNT {
+                        MemoParseStage::OutCountVarint
+                    } else {
+                        MemoParseStage::LenVarint
+                    };
                 }
-                TAG_FEE => {
-                    if val.len() != 8 {
-                        return Err(AppSW::MemoInvalid);
+                MemoParseStage::OutCountVarint => {
+                    match read_leb128_resumable(data, 0, self.varint_partial, self.varint_shift)? {
+                        Leb128Step::Done(val, consumed) => {
+                            data = &data[consumed..];
+                            self.expected_outs = Some(val);
+                            self.stage = MemoParseStage::Tag;
+                        }
+                        Leb128Step::NeedMore { partial, shift } => {
+                            self.varint_partial = partial;
+                            self.varint_shift = shift;
+                            data = &[];
+                        }
                     }
-                    fee = u64::from_le_bytes(val.try_into().unwrap());
                 }
-                TAG_NONCE => {
-                    if val.len() != 8 {
-                        return Err(AppSW::MemoInvalid);
+                MemoParseStage::LenVarint => {
+                    match read_leb128_resumable(data, 0, self.varint_partial, self.varint_shift)? {
+                        Leb128Step::Done(val, consumed) => {
+                            data = &data[consumed..];
+                            if val as usize > MAX_MEMO_RECORD_LEN {
+                                return Err(AppSW::MemoInvalid);
+                            }
+                            self.record_len = val as usize;
+                            self.record_buf.clear();
+                            self.stage = if self.record_len == 0 {
+                                self.dispatch_record()?;
+                                MemoParseStage::Tag
+                            } else {
+                                MemoParseStage::Value
+                            };
+                        }
+                        Leb128Step::NeedMore { partial, shift } => {
+                            self.varint_partial = partial;
+                            self.varint_shift = shift;
+                            data = &[];
+                        }
                     }
-                    nonce = u64::from_le_bytes(val.try_into().unwrap());
                 }
-                TAG_ASSET_TABLE => {
-                    // Parse asset table: count(varint) | asset1(32) | asset2(32) | ...
-                    let mut p = 0usize;
-                    let (asset_count, pn) = read_leb128(val, p)?;
-                    p = pn;
-                    
-                    // Validate we have enough bytes for all assets
-                    if p + (asset_count as usize * 32) > val.len() {
-                        return Err(AppSW::MemoInvalid);
-                    }
-                    
-                    // Read each asset
-                    for _ in 0..asset_count {
-                        let mut asset = [0u8; 32];
-                        asset.copy_from_slice(&val[p..p + 32]);
-                        p += 32;
-                        ws.asset_table.push(asset);
-                    }
-                    
-                    // Limit check (max 255 non-native assets since we use u8 index)
-                    if ws.asset_table.len() > 255 {
-                        return Err(AppSW::MemoInvalid);
+                MemoParseStage::Value => {
+                    let needed = self.record_len - self.record_buf.len();
+                    let take = needed.min(data.len());
+                    self.record_buf.extend_from_slice(&data[..take]);
+                    data = &data[take..];
+                    if self.record_buf.len() == self.record_len {
+                        self.dispatch_record()?;
+                        self.stage = MemoParseStage::Tag;
                     }
                 }
-                TAG_OUT_ITEM => {
-                    // Modified format: asset_index(1) | dest(32) | amount(8) | extra_len(varint) | preview_len(varint) | preview_bytes
-                    if val.len() < 1 + 32 + 8 {
-                        return Err(AppSW::MemoInvalid);
-                    }
-                    let mut p = 0usize;
-                    
-                    // Asset index (0 = native, 1+ = index into asset_table)
-                    let asset_index = val[p];
-                    p += 1;
-                    
-                    // Validate index
-                    if asset_index > 0 && (asset_index as usize) > ws.asset_table.len() {
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses `self.record_buf` (the just-completed record's value) per
+    /// `self.tag` and folds the result straight into `MemoWorkspace` or this
+    /// parser's scalar fields - the same per-tag bodies the old whole-buffer
+    /// `parse_memo_tlv` used, just fed one already-assembled record at a time.
+    fn dispatch_record(&mut self) -> Result<(), AppSW> {
+        let val = &self.record_buf[..];
+        let ws = memo_ws_mut();
+
+        match self.tag {
+            TAG_TX_TYPE => {
+                if val.len() != 1 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                self.tx_type = val[0];
+            }
+            TAG_FEE => {
+                if val.len() != 8 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                self.fee = u64::from_le_bytes(val.try_into().unwrap());
+            }
+            TAG_NONCE => {
+                if val.len() != 8 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                self.nonce = u64::from_le_bytes(val.try_into().unwrap());
+            }
+            TAG_ASSET_TABLE => {
+                // count(varint) | [asset(32) | decimals(1) | ticker_len(varint) | ticker_bytes] ...
+                let mut p = 0usize;
+                let (asset_count, pn) = read_leb128(val, p)?;
+                p = pn;
+
+                for _ in 0..asset_count {
+                    if p + 32 + 1 > val.len() {
                         return Err(AppSW::MemoInvalid);
                     }
-                    
-                    let mut dest = [0u8; 32];
-                    dest.copy_from_slice(&val[p..p + 32]);
+                    let mut asset = [0u8; 32];
+                    asset.copy_from_slice(&val[p..p + 32]);
                     p += 32;
-                    
-                    let amount = u64::from_le_bytes(val[p..p + 8].try_into().unwrap());
-                    p += 8;
-                    
-                    let (extra_len, pn1) = read_leb128(val, p)?;
-                    p = pn1;
-                    let (preview_len, pn2) = read_leb128(val, p)?;
+
+                    let decimals = val[p];
+                    p += 1;
+
+                    let (ticker_len, pn2) = read_leb128(val, p)?;
                     p = pn2;
-                    
-                    if p + (preview_len as usize) > val.len() {
-                        return Err(AppSW::MemoInvalid);
-                    }
-                    let preview = val[p..p + (preview_len as usize)].to_vec();
-
-                    ws.outs.push(MemoOut {
-                        asset_index,
-                        dest,
-                        amount,
-                        extra_len,
-                        preview,
-                    });
-                },
-                TAG_BURN => {
-                    if val.len() < 1 + 8 { return Err(AppSW::MemoInvalid); }
-                    let asset_index = val[0];
-                    if asset_index > 0 && (asset_index as usize) > ws.asset_table.len() {
+                    if p + (ticker_len as usize) > val.len() {
                         return Err(AppSW::MemoInvalid);
                     }
-                    let amount = u64::from_le_bytes(val[1..9].try_into().unwrap());
-                    let mut p = 9;
-                    let (pv_len, pn) = read_leb128(val, p)?; p = pn;
-                    if p + (pv_len as usize) > val.len() { return Err(AppSW::MemoInvalid); }
-                    let preview = val[p..p + pv_len as usize].to_vec();
+                    let ticker = val[p..p + (ticker_len as usize)].to_vec();
+                    p += ticker_len as usize;
 
-                    ws.burn = Some(MemoBurn { asset_index, amount });
+                    ws.asset_table.push(AssetInfo { asset, decimals, ticker });
                 }
-                _ => {
-                    // Unknown tag: ignore (forward compatible)
+
+                // Limit check (max 255 non-native assets since we use u8 index)
+                if ws.asset_table.len() > 255 {
+                    return Err(AppSW::MemoInvalid);
                 }
             }
+            TAG_OUT_ITEM => {
+                // asset_index(1) | dest(32) | amount(8) | extra_len(varint) | preview_len(varint) | preview_bytes
+                if val.len() < 1 + 32 + 8 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let mut p = 0usize;
+
+                let asset_index = val[p];
+                p += 1;
+                if asset_index > 0 && (asset_index as usize) > ws.asset_table.len() {
+                    return Err(AppSW::MemoInvalid);
+                }
+
+                let mut dest = [0u8; 32];
+                dest.copy_from_slice(&val[p..p + 32]);
+                p += 32;
+
+                let amount = u64::from_le_bytes(val[p..p + 8].try_into().unwrap());
+                p += 8;
+
+                let (extra_len, pn1) = read_leb128(val, p)?;
+                p = pn1;
+                let (preview_len, pn2) = read_leb128(val, p)?;
+                p = pn2;
+
+                if p + (preview_len as usize) > val.len() {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let preview = val[p..p + (preview_len as usize)].to_vec();
+
+                ws.outs.push(MemoOut {
+                    asset_index,
+                    dest,
+                    amount,
+                    extra_len,
+                    preview,
+                });
+            }
+            TAG_BURN => {
+                if val.len() < 1 + 8 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let asset_index = val[0];
+                if asset_index > 0 && (asset_index as usize) > ws.asset_table.len() {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let amount = u64::from_le_bytes(val[1..9].try_into().unwrap());
+                let mut p = 9;
+                let (pv_len, pn) = read_leb128(val, p)?;
+                p = pn;
+                if p + (pv_len as usize) > val.len() {
+                    return Err(AppSW::MemoInvalid);
+                }
+
+                ws.burn = Some(MemoBurn { asset_index, amount });
+            }
+            TAG_INVOKE => {
+                if val.len() < 32 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let mut contract = [0u8; 32];
+                contract.copy_from_slice(&val[..32]);
+                let mut p = 32;
+
+                let (entrypoint, pn) = read_leb128(val, p)?;
+                p = pn;
+
+                let (deposits, pn) = parse_deposits(val, p, ws.asset_table.len())?;
+                p = pn;
+
+                let (preview_len, pn) = read_leb128(val, p)?;
+                p = pn;
+                if preview_len as usize > MAX_INVOKE_PARAMS_PREVIEW_LEN
+                    || p + (preview_len as usize) > val.len()
+                {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let params_preview = val[p..p + preview_len as usize].to_vec();
+
+                ws.invoke = Some(MemoInvoke {
+                    contract,
+                    entrypoint,
+                    deposits,
+                    params_preview,
+                });
+            }
+            TAG_DEPLOY => {
+                if val.len() < 32 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let mut module_commitment = [0u8; 32];
+                module_commitment.copy_from_slice(&val[..32]);
+                let mut p = 32;
+
+                let (module_size, pn) = read_leb128(val, p)?;
+                p = pn;
+
+                let (deposits, _) = parse_deposits(val, p, ws.asset_table.len())?;
+
+                ws.deploy = Some(MemoDeploy {
+                    module_size,
+                    module_commitment,
+                    deposits,
+                });
+            }
+            TAG_MULTISIG => {
+                if val.len() < 2 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let threshold = val[0];
+                let participants_count = val[1] as usize;
+                let mut p = 2usize;
+                if p + participants_count * 32 != val.len() {
+                    return Err(AppSW::MemoInvalid);
+                }
+
+                let mut participants = Vec::with_capacity(participants_count);
+                for _ in 0..participants_count {
+                    let mut pubkey = [0u8; 32];
+                    pubkey.copy_from_slice(&val[p..p + 32]);
+                    p += 32;
+                    participants.push(pubkey);
+                }
+
+                ws.multisig = Some(MemoMultisig { threshold, participants });
+            }
+            TAG_WITNESS_HASH => {
+                if val.len() != 64 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let mut wh = [0u8; 64];
+                wh.copy_from_slice(val);
+                self.witness_hash = Some(wh);
+            }
+            TAG_NET_COMMITMENT => {
+                if val.len() != 32 {
+                    return Err(AppSW::MemoInvalid);
+                }
+                let mut nc = [0u8; 32];
+                nc.copy_from_slice(val);
+                self.net_commitment = Some(nc);
+            }
+            _ => {
+                // Unknown tag: ignore (forward compatible)
+            }
         }
 
-        if let Some(n) = expected_outs {
+        Ok(())
+    }
+
+    /// Called once `load_memo`'s final chunk has been fed in: checks no
+    /// record was left straddling the end of the stream and that every
+    /// tx-type-specific field the preview requires was actually present.
+    pub fn finalize(&self) -> Result<MemoPreview, AppSW> {
+        if self.stage != MemoParseStage::Tag {
+            return Err(AppSW::MemoInvalid);
+        }
+
+        let ws = memo_ws_mut();
+
+        if let Some(n) = self.expected_outs {
             if ws.outs.len() as u64 != n {
                 return Err(AppSW::MemoInvalid);
             }
         }
 
+        if self.tx_type == TX_TRANSFER && self.net_commitment.is_none() {
+            return Err(AppSW::MemoInvalid);
+        }
+
+        if self.tx_type == TX_INVOKE_CONTRACT && ws.invoke.is_none() {
+            return Err(AppSW::MemoInvalid);
+        }
+
+        if self.tx_type == TX_DEPLOY_CONTRACT && ws.deploy.is_none() {
+            return Err(AppSW::MemoInvalid);
+        }
+
+        if self.tx_type == TX_MULTISIG && ws.multisig.is_none() {
+            return Err(AppSW::MemoInvalid);
+        }
+
         Ok(MemoPreview {
-            tx_type,
-            fee,
-            nonce,
+            tx_type: self.tx_type,
+            fee: self.fee,
+            nonce: self.nonce,
+            witness_hash: self.witness_hash,
+            net_commitment: self.net_commitment,
         })
     }
 }
 
+impl Default for MemoStreamParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub const TX_BURN: u8 = 0;
 pub const TX_TRANSFER: u8 = 1;
 pub const TX_MULTISIG: u8 = 2;